@@ -0,0 +1,49 @@
+//! Token escrow primitives shared by the auction variants.
+//!
+//! Every movement of value through the contract funnels through these
+//! helpers so the transfer-in / refund / payout patterns stay in one place
+//! as more auction kinds are added.
+
+use soroban_sdk::{token, Address, Env};
+
+/// Pull `amount` of `token` from `from` into the contract's custody.
+pub(crate) fn lock(env: &Env, token: &Address, from: &Address, amount: i128) {
+    if amount > 0 {
+        token::Client::new(env, token).transfer(from, env.current_contract_address(), &amount);
+    }
+}
+
+/// Like `lock`, but spends a pre-approved allowance with the contract as
+/// spender instead of requiring `from`'s direct authorization.
+pub(crate) fn lock_from(env: &Env, token: &Address, from: &Address, amount: i128) {
+    if amount > 0 {
+        token::Client::new(env, token).transfer_from(
+            &env.current_contract_address(),
+            from,
+            &env.current_contract_address(),
+            &amount,
+        );
+    }
+}
+
+/// Push `amount` of escrowed `token` out to `to`. A failed transfer aborts
+/// the invocation.
+pub(crate) fn release(env: &Env, token: &Address, to: &Address, amount: i128) {
+    if amount > 0 {
+        token::Client::new(env, token).transfer(&env.current_contract_address(), to, &amount);
+    }
+}
+
+/// Fallible variant of `release` for the pull-fallback paths: a transfer the
+/// recipient (or token) rejects surfaces as `Err` so the caller can park the
+/// funds as a pending claim instead of aborting.
+pub(crate) fn try_release(env: &Env, token: &Address, to: &Address, amount: i128) -> Result<(), ()> {
+    if amount <= 0 {
+        return Ok(());
+    }
+    match token::Client::new(env, token).try_transfer(&env.current_contract_address(), to, &amount)
+    {
+        Ok(Ok(())) => Ok(()),
+        _ => Err(()),
+    }
+}
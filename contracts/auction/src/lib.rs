@@ -0,0 +1,3530 @@
+#![no_std]
+// Events are published as raw topic tuples; the typed #[contractevent] API
+// will replace them when the event schema is migrated wholesale.
+#![allow(deprecated)]
+
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, symbol_short, token, Address, Env,
+    IntoVal, Map, Symbol, Vec,
+};
+
+/// Auction formats supported by the contract.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum AuctionKind {
+    /// Ascending price; the highest bid at close wins.
+    English,
+    /// Price decays from `buy_now_price` down to `reserve_price`; the first
+    /// taker wins at the current price.
+    Dutch,
+    /// Second-price: the highest bidder wins but pays the runner-up's price,
+    /// and claims the asset after settlement.
+    Vickrey,
+    /// Multi-unit lot sold off in parts at `reserve_price` per unit.
+    Divisible,
+    /// Promotional format: the lot is split 70/30 between the top two
+    /// distinct bidders, each paying their own bid.
+    SplitTopTwo,
+    /// Seller-pays disposal: bidders compete down on the payment they will
+    /// accept for taking the asset, funded from a budget (`reserve_price`)
+    /// the seller escrows at creation.
+    Reverse,
+}
+
+/// How outbid bidders get their escrow back.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum RefundMode {
+    /// Outbid bidders are refunded inline during `place_bid`.
+    Immediate,
+    /// Refunds accrue to a pending balance and are pulled via `withdraw`.
+    Pull,
+    /// Refunds park as pending claims and are pushed out opportunistically
+    /// on later successful operations, so a recipient whose account rejects
+    /// transfers can never hold up the bid that displaced them.
+    Deferred,
+}
+
+/// Automatic re-opening of no-sale auctions for another full window.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RelistConfig {
+    /// Re-open instead of returning the asset when nothing sold.
+    pub auto: bool,
+    /// Maximum number of automatic re-openings.
+    pub count: u32,
+}
+
+/// A share of sale proceeds directed to a third party at settlement.
+/// Disabled when `bps` is 0.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RevenueShare {
+    pub recipient: Option<Address>,
+    /// Basis points of the final price this share takes.
+    pub bps: u32,
+}
+
+/// Discrete Dutch decay: with a nonzero interval the price holds flat for
+/// `interval` seconds and then drops by `amount` at each boundary (never
+/// below the floor) instead of decaying continuously.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DutchStep {
+    /// Seconds each price level holds; 0 keeps the continuous linear decay.
+    pub interval: u64,
+    /// How far the price drops at each interval boundary.
+    pub amount: i128,
+}
+
+/// Winner-side installment terms for English auctions. With a nonzero
+/// deadline, settlement retains only a down payment out of the winning
+/// escrow and refunds the rest; the hammer price then arrives through
+/// `pay_installment` while the asset stays locked, with `default_installment`
+/// closing out buyers who miss the deadline.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InstallmentPlan {
+    /// Share of the hammer price retained at settlement as the down payment.
+    pub down_bps: u32,
+    /// Seconds after settlement the winner has to finish paying; 0 disables
+    /// installment purchases entirely.
+    pub deadline: u64,
+    /// Share of the paid-in principal returned to the winner on default;
+    /// the remainder (and the asset) goes to the seller.
+    pub refund_bps: u32,
+}
+
+/// Settlement and claim scheduling around the end of bidding.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TimingConfig {
+    /// Grace period after `end_time` before `settle` is allowed.
+    pub settle_delay: u64,
+    /// Minimum seconds between the final bid and settlement. Distinct from
+    /// anti-snipe: it never moves `end_time`, it only delays `settle`.
+    /// 0 disables.
+    pub quiet_period: u64,
+    /// How long after settlement a pull-claimed asset stays reserved for the
+    /// winner; past it the seller may reclaim. 0 means forever.
+    pub claim_deadline: u64,
+}
+
+/// Oracle-enforced reserve floor. With an oracle set, bids must be worth at
+/// least `reserve` reference units at the oracle's time-weighted price; the
+/// oracle exposes `twap() -> (price, decimals, timestamp)`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TwapConfig {
+    pub oracle: Option<Address>,
+    /// Reserve denominated in the oracle's reference units.
+    pub reserve: i128,
+    /// Maximum acceptable age of the TWAP observation, in seconds.
+    pub max_age: u64,
+}
+
+/// Anti-snipe: how bids landing near the scheduled end stretch the auction.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AntiSnipeConfig {
+    /// A bid landing this close to the end extends the auction. 0 disables.
+    pub window: u64,
+    /// How far a qualifying late bid pushes `end_time` out.
+    pub period: u64,
+    /// Only extend for bids that could actually win: ones meeting the soft
+    /// reserve (when set). Sub-reserve activity then never stretches the
+    /// schedule.
+    pub above_reserve_only: bool,
+}
+
+/// How a bid that merely matches the current highest is resolved.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum TieBreak {
+    /// The sitting leader keeps the lead; an equal bid is rejected.
+    FirstBid,
+    /// An equal bid takes the lead when its bidder has posted the larger
+    /// good-faith deposit (see `top_up_deposit`).
+    HighestDeposit,
+}
+
+/// Creation parameters for a new auction.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AuctionParams {
+    pub kind: AuctionKind,
+    /// Discovery category the auction is indexed under (see
+    /// `list_by_category`); the empty symbol leaves it unindexed.
+    pub category: Symbol,
+    pub seller: Address,
+    /// Co-ownership split of the seller's cut of the proceeds:
+    /// `(recipient, share_bps)` entries that must sum to exactly 10_000.
+    /// Empty sends the whole cut to `seller`.
+    pub co_sellers: Vec<(Address, u32)>,
+    /// Token contract holding the auctioned asset.
+    pub asset: Address,
+    pub asset_amount: i128,
+    /// Token bids are denominated in.
+    pub bid_token: Address,
+    /// Minimum opening bid. For `Dutch` this is the floor price; for
+    /// `Divisible` the per-unit price.
+    pub reserve_price: i128,
+    /// TWAP-enforced reserve: when an oracle is set, bids must also be worth
+    /// the configured reference value at its time-weighted price.
+    pub twap: TwapConfig,
+    /// Barter-style reserve: when nonzero the effective reserve is
+    /// `asset_amount * reserve_ratio` (bid units per asset unit), overriding
+    /// `reserve_price` for bidding floors.
+    pub reserve_ratio: i128,
+    /// Instant-purchase price; 0 disables. For `Dutch` this is the starting
+    /// price the decay begins from (required).
+    pub buy_now_price: i128,
+    /// Stepped decay schedule for `Dutch` auctions; a zero interval decays
+    /// continuously.
+    pub dutch_step: DutchStep,
+    /// Ledger timestamp bidding opens at; 0 means immediately.
+    pub start_time: u64,
+    /// Length of the bidding window in seconds from the start time.
+    pub duration: u64,
+    /// Minimum amount a bid must exceed the current highest bid by.
+    pub min_increment: i128,
+    /// Seller's private minimum checked only at settlement: any bid clearing
+    /// the (typically nominal) reserve is accepted, but if the final bid is
+    /// below this the asset goes back to the seller and the bidder is
+    /// refunded. 0 disables.
+    pub soft_reserve: i128,
+    /// Raises the opening-bid floor this many basis points above the
+    /// reserve, discouraging lowball first bids. 0 keeps the floor at the
+    /// reserve. Later bids only follow the increment rules.
+    pub opening_bid_bps: u32,
+    /// Stepped increment table of `(threshold, increment)` pairs sorted by
+    /// ascending threshold: the increment of the last entry at or below the
+    /// current bid applies, overriding `min_increment`. Empty disables.
+    pub increment_schedule: Vec<(i128, i128)>,
+    /// Bids must be a multiple of this when nonzero.
+    pub bid_tick: i128,
+    /// Anti-snipe behavior for bids landing near the end.
+    pub anti_snipe: AntiSnipeConfig,
+    /// Flat amount a leader forfeits to the seller when retracting a live
+    /// bid via `retract_bid`. 0 makes retraction free.
+    pub retraction_penalty: i128,
+    /// Settlement and claim scheduling around the end of bidding.
+    pub timing: TimingConfig,
+    /// Installment purchase terms for the winner; a zero deadline disables.
+    pub installments: InstallmentPlan,
+    pub refund_mode: RefundMode,
+    /// Good-faith deposit taken from each first-time bidder; 0 disables.
+    /// Losing bidders are refunded at settle; the winner's deposit is
+    /// forfeited to the seller.
+    pub deposit: i128,
+    /// Royalty carved out of sale proceeds for a creator or rights holder.
+    pub royalty: RevenueShare,
+    /// Public-goods donation carved out of sale proceeds.
+    pub donation: RevenueShare,
+    /// Portion of proceeds burned out of circulation at settlement via the
+    /// bid token's `burn`. 0 disables.
+    pub burn_bps: u32,
+    /// Auction-house premium the buyer pays on top of each bid, escrowed
+    /// with the bid and paid to the fee collector on the final price. The
+    /// seller's hammer price is unaffected. 0 disables.
+    pub buyer_premium_bps: u32,
+    /// Hide the leading amount from `get_highest_bid` while the auction is
+    /// live; it is revealed once the auction ends or settles. Internal logic
+    /// always uses the real amount.
+    pub hide_highest: bool,
+    /// Automatic re-opening of no-sale auctions instead of returning the
+    /// asset.
+    pub relist: RelistConfig,
+    /// Treat `asset` as an NFT contract and lazily mint to the winner at
+    /// settlement instead of escrowing anything up front: `settle` invokes
+    /// `mint(winner, asset_amount)` on it, with `asset_amount` serving as
+    /// the token id. No-sale outcomes mint nothing. English-only, and
+    /// incompatible with bundles and external escrow custody.
+    pub mint_on_settle: bool,
+    /// Require bids to be whole tokens of the bid asset (per its
+    /// `decimals()`), rejecting sub-unit dust amounts.
+    pub whole_units_only: bool,
+    /// How bids exactly matching the current highest are resolved.
+    pub tie_break: TieBreak,
+    /// External custodian for the primary asset: when set, the locked asset
+    /// is parked there instead of in this contract and pulled back with
+    /// `transfer_from` (the custodian must approve this contract) when it is
+    /// due for delivery. Bundle lots stay self-custodied.
+    pub escrow_address: Option<Address>,
+    /// Extra increment tacked on per distinct bidder beyond the first, so
+    /// the bar rises as the field grows. 0 disables.
+    pub minimum_escalation: i128,
+    /// Shift `end_time` forward by the paused duration when a seller pause
+    /// lifts, so paused time doesn't eat the bidding window.
+    pub freeze_clock_on_pause: bool,
+    /// Cap on the bid-token value this auction may hold in escrow at once;
+    /// bids that would push the held amount past it are rejected. `None`
+    /// leaves escrow unbounded.
+    pub max_escrow: Option<i128>,
+    /// Only these addresses may bid when non-empty.
+    pub whitelist: Vec<Address>,
+    /// Extra (token, amount) lots escrowed and delivered together with the
+    /// primary asset. Not supported for `Divisible` auctions.
+    pub bundle: Vec<(Address, i128)>,
+}
+
+/// Immutable record of how an auction ended. Written once at settlement and
+/// designed to outlive the rest of the per-auction state.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Outcome {
+    pub id: u64,
+    pub seller: Address,
+    /// `None` for no-sales and cancellations.
+    pub winner: Option<Address>,
+    /// Bid-token proceeds the sale closed at; 0 for no-sales.
+    pub final_price: i128,
+    pub settled_at: u64,
+}
+
+/// Contract-wide escrow obligations grouped by purpose, for reconciling the
+/// contract's token balances against what it owes. Amounts are summed across
+/// tokens per bucket.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EscrowBreakdown {
+    /// Asset units still escrowed and unallocated, across live auctions and
+    /// unclaimed Vickrey wins.
+    pub asset_locked: i128,
+    /// Live bid escrow: leading bids, funded runners-up, and accumulated
+    /// divisible proceeds.
+    pub highest_bid_held: i128,
+    /// Pull-mode and parked refunds awaiting `withdraw`, minus the fee
+    /// collector's share.
+    pub pending_refunds_total: i128,
+    /// Good-faith deposits still held for live auctions.
+    pub deposits_total: i128,
+    /// Fee-collector payouts that could not be delivered and are parked as
+    /// pending claims.
+    pub accrued_fees: i128,
+}
+
+/// Contract-wide dashboard aggregates, maintained incrementally at creation,
+/// first bids, and settlement so `get_stats` never scans per-auction state.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Stats {
+    pub total_created: u64,
+    pub total_settled: u64,
+    /// Cumulative proceeds across every settled sale.
+    pub total_volume: i128,
+    /// Auctions created but not yet settled.
+    pub active_count: u32,
+    /// Distinct bidders summed per auction (an address bidding on two
+    /// auctions counts twice).
+    pub total_bidders: u64,
+}
+
+/// Public view of the leading bid, shaped so privacy-mode auctions can
+/// acknowledge a bid exists without revealing its size.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HighestBidView {
+    pub has_bid: bool,
+    pub bidder: Option<Address>,
+    /// `None` while a `hide_highest` auction is live (or when no bid).
+    pub amount: Option<i128>,
+}
+
+/// Everything a detail page needs in one read: the stored record, the
+/// lifecycle flags the individual getters derive from it, and the
+/// config-derived bidding constraints, so clients make one RPC call instead
+/// of half a dozen.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AuctionSnapshot {
+    pub auction: Auction,
+    pub settled: bool,
+    pub highest: HighestBidView,
+    /// Seconds until the (possibly extended) end; 0 once past it.
+    pub time_remaining: u64,
+    pub progress_bps: u32,
+    pub reserve_met: bool,
+    /// Smallest amount `place_bid` would accept right now, before ticks and
+    /// per-bidder escalation.
+    pub minimum_next_bid: i128,
+}
+
+/// Live state of a single auction.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Auction {
+    pub params: AuctionParams,
+    pub start_time: u64,
+    pub end_time: u64,
+    pub highest_bidder: Option<Address>,
+    pub highest_bid: i128,
+    /// Where the leader's escrow goes if they are outbid or refunded;
+    /// defaults to the bidder themselves.
+    pub refund_to: Option<Address>,
+    pub second_bidder: Option<Address>,
+    pub second_bid: i128,
+    pub last_bid_time: u64,
+    /// Ledger timestamp the auction settled at; 0 while open.
+    pub settled_at: u64,
+    /// Units of the asset still escrowed and unallocated.
+    pub remaining: i128,
+    /// Bid-token proceeds accumulated before settlement (divisible fills).
+    pub proceeds: i128,
+    /// How many automatic relists this auction has consumed.
+    pub relists_done: u32,
+    /// Ledger timestamp the seller paused bidding at; 0 while bidding is
+    /// open. Independent of the admin-wide pause switch.
+    pub seller_paused_at: u64,
+    /// How many distinct addresses have bid so far.
+    pub distinct_bidders: u32,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Admin,
+    FeeCollector,
+    FeeBps,
+    Paused,
+    Counter,
+    Auction(u64),
+    IsSettle(u64),
+    /// Pending pull-refund balances for an address, keyed by token.
+    PendingRefund(Address),
+    /// Addresses with a nonzero pending refund, consumed by `refund_all`.
+    PendingAddrs,
+    Deposit(u64, Address),
+    Depositors(u64),
+    /// Ids of every auction created by a seller, in creation order.
+    SellerAuctions(Address),
+    /// Ids of every auction created under a category, in creation order.
+    Category(Symbol),
+    /// Cumulative bid-token proceeds across every settled sale.
+    TotalVolume,
+    /// Cap on concurrently active auctions; absent means unlimited.
+    MaxActive,
+    /// Number of auctions created but not yet settled.
+    ActiveCount,
+    /// Number of auctions ever settled, for the stats view.
+    SettledCount,
+    /// Cumulative distinct bidders across all auctions, for the stats view.
+    TotalBidders,
+    /// Optional KYC attestation contract every bidder must pass.
+    KycContract,
+    /// Tiered `(price threshold, bps)` fee schedule overriding `FeeBps`.
+    FeeSchedule,
+    /// Set once an address has actually received refund money back.
+    Refunded(Address),
+    /// Distinct addresses that have bid on an auction, for the escalating
+    /// minimum and bidder-count queries.
+    Bidders(u64),
+    /// Set once an address has won any auction here, for reputation lookups.
+    HasWon(Address),
+    /// Deposit the winner forfeited at settlement, for total-cost queries.
+    ForfeitedDeposit(u64),
+    /// Cumulative amount the winner has paid toward an installment sale,
+    /// down payment included. Removed once fully paid or defaulted.
+    Installment(u64),
+    /// Contract-wide `(fee, min_bidders)` listing-fee policy charged to
+    /// sellers at creation; absent means listings are free.
+    ListingFeeConfig,
+    /// Seconds a seller must wait after cancelling before creating again;
+    /// absent disables the cooldown.
+    RelistCooldown,
+    /// Ledger timestamp of a seller's most recent cancellation.
+    LastCancel(Address),
+    /// Escrow-interest bonus rate for displaced bidders, in bps of the
+    /// locked amount per day; absent disables the bonus.
+    BonusRate,
+    /// Token-seconds an address's bid escrow spent locked in an auction,
+    /// accumulated as they are displaced and paid out at settlement.
+    LockAccrual(u64, Address),
+    /// Listing fee escrowed from an auction's seller, with the bidder count
+    /// that releases it back: `(fee, min_bidders)`.
+    ListingFee(u64),
+    /// Idempotency nonces consumed by `create_with_nonce`, per seller.
+    UsedNonce(Address, u64),
+    /// Bid tokens auctions may be denominated in; absent means
+    /// unrestricted. Installed by the admin — typically the factory that
+    /// deployed this instance, passing its own allowed set down.
+    AllowedBidTokens,
+    /// Set once `migrate` has rewritten pre-multi-auction state.
+    Migrated,
+    /// Final outcome of a settled auction, written once at settlement.
+    Outcome(u64),
+}
+
+/// Storage layout of the single-auction deployments that predate the
+/// id-keyed refactor, kept only so `migrate` can read their state.
+#[contracttype]
+#[derive(Clone)]
+enum LegacyKey {
+    Auction,
+    IsSettled,
+}
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum AuctionError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    ContractPaused = 3,
+    AuctionNotFound = 4,
+    InvalidParams = 5,
+    /// End-time arithmetic overflowed `u64`.
+    TimeOverflow = 6,
+    NotStarted = 7,
+    AuctionEnded = 8,
+    AuctionNotEnded = 9,
+    AlreadySettled = 10,
+    BidTooLow = 11,
+    BidNotOnTick = 12,
+    NotWhitelisted = 13,
+    NoBuyNow = 14,
+    NothingToWithdraw = 15,
+    WrongKind = 16,
+    NotWinner = 17,
+    InsufficientRemaining = 18,
+    MathOverflow = 19,
+    DuplicateAsset = 20,
+    TooManyActiveAuctions = 21,
+    AlreadyStarted = 22,
+    NotVerified = 23,
+    NonceUsed = 24,
+    /// An `end_time` write would not leave it strictly after `start_time`.
+    InvalidTimeRange = 25,
+    /// `cleanup` was called while escrowed value is still claimable.
+    ClaimsOutstanding = 26,
+    /// The contract no longer holds the escrowed asset it is meant to
+    /// deliver.
+    AssetMissing = 27,
+    /// The TWAP observation is older than the configured maximum age.
+    StaleOracle = 28,
+    /// The bid would push the auction's escrowed bid token over `max_escrow`.
+    EscrowCapExceeded = 29,
+    /// The seller has paused bidding on this auction.
+    SellerPaused = 30,
+    /// The requested schedule would produce an end time that has already
+    /// passed.
+    EndInPast = 31,
+    /// The seller cancelled too recently and is still in the relist
+    /// cooldown.
+    CooldownActive = 32,
+    /// The auction's bid token is not in the instance's allowed set.
+    BidTokenNotAllowed = 33,
+}
+
+mod escrow;
+
+/// The reserve bids are measured against: the flat `reserve_price`, unless a
+/// `reserve_ratio` derives it from the lot size instead.
+fn effective_reserve(params: &AuctionParams) -> i128 {
+    if params.reserve_ratio > 0 {
+        params.asset_amount * params.reserve_ratio
+    } else {
+        params.reserve_price
+    }
+}
+
+/// The bid-token floor a TWAP-reserved auction demands right now: the
+/// configured reference-unit reserve converted at the oracle's time-weighted
+/// price, rounded up so the reserve is never undershot. Errors if the
+/// observation is too old to trust.
+fn twap_floor(env: &Env, params: &AuctionParams) -> Result<i128, AuctionError> {
+    let oracle = match &params.twap.oracle {
+        Some(oracle) => oracle,
+        None => return Ok(0),
+    };
+    let (price, decimals, observed_at): (i128, u32, u64) =
+        env.invoke_contract(oracle, &symbol_short!("twap"), Vec::new(env));
+    if env.ledger().timestamp().saturating_sub(observed_at) > params.twap.max_age {
+        return Err(AuctionError::StaleOracle);
+    }
+    if price <= 0 {
+        return Err(AuctionError::StaleOracle);
+    }
+    let scale = 10i128
+        .checked_pow(decimals)
+        .ok_or(AuctionError::MathOverflow)?;
+    let value = params
+        .twap
+        .reserve
+        .checked_mul(scale)
+        .ok_or(AuctionError::MathOverflow)?;
+    Ok((value + price - 1) / price)
+}
+
+/// Basis-point helper: `amount * bps / 10_000`, flooring.
+///
+/// Flooring is the uniform rounding direction for every deduction (fee,
+/// royalty, burn, buyer premium): each carved-out share rounds down and the
+/// seller receives the exact remainder, so the pieces can never sum to more
+/// than the escrowed total.
+fn bps_of(amount: i128, bps: u32) -> i128 {
+    amount * i128::from(bps) / 10_000
+}
+
+/// Single authority for end-time arithmetic. Every code path that derives or
+/// moves an `end_time` (creation, manual extension, anti-snipe) must go
+/// through here so overflow surfaces as a clean error instead of a panic.
+fn compute_end_time(base: u64, delta: u64) -> Result<u64, AuctionError> {
+    base.checked_add(delta).ok_or(AuctionError::TimeOverflow)
+}
+
+/// Single authority for `end_time` writes on a live auction: a window that
+/// does not end strictly after it starts is degenerate, so every mutation
+/// funnels through this guard.
+fn apply_end_time(auction: &mut Auction, end_time: u64) -> Result<(), AuctionError> {
+    if end_time <= auction.start_time {
+        return Err(AuctionError::InvalidTimeRange);
+    }
+    auction.end_time = end_time;
+    Ok(())
+}
+
+fn read_auction(env: &Env, id: u64) -> Result<Auction, AuctionError> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Auction(id))
+        .ok_or(AuctionError::AuctionNotFound)
+}
+
+fn write_auction(env: &Env, id: u64, auction: &Auction) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::Auction(id), auction);
+}
+
+fn settled(env: &Env, id: u64) -> bool {
+    env.storage()
+        .persistent()
+        .get(&DataKey::IsSettle(id))
+        .unwrap_or(false)
+}
+
+fn mark_settled(env: &Env, id: u64) {
+    env.storage().persistent().set(&DataKey::IsSettle(id), &true);
+    let active: u32 = env
+        .storage()
+        .instance()
+        .get(&DataKey::ActiveCount)
+        .unwrap_or(0);
+    env.storage()
+        .instance()
+        .set(&DataKey::ActiveCount, &active.saturating_sub(1));
+    let settled: u64 = env
+        .storage()
+        .instance()
+        .get(&DataKey::SettledCount)
+        .unwrap_or(0);
+    env.storage()
+        .instance()
+        .set(&DataKey::SettledCount, &(settled + 1));
+}
+
+/// Stamp the permanent outcome record for a freshly settled auction.
+fn record_outcome(env: &Env, id: u64, auction: &Auction, winner: Option<Address>, price: i128) {
+    if let Some(w) = &winner {
+        env.storage()
+            .persistent()
+            .set(&DataKey::HasWon(w.clone()), &true);
+    }
+    let outcome = Outcome {
+        id,
+        seller: auction.params.seller.clone(),
+        winner,
+        final_price: price,
+        settled_at: auction.settled_at,
+    };
+    env.storage().persistent().set(&DataKey::Outcome(id), &outcome);
+}
+
+fn require_not_paused(env: &Env) -> Result<(), AuctionError> {
+    let paused: bool = env
+        .storage()
+        .instance()
+        .get(&DataKey::Paused)
+        .unwrap_or(false);
+    if paused {
+        return Err(AuctionError::ContractPaused);
+    }
+    Ok(())
+}
+
+fn fee_config(env: &Env) -> Result<(Address, u32), AuctionError> {
+    let collector = env
+        .storage()
+        .instance()
+        .get(&DataKey::FeeCollector)
+        .ok_or(AuctionError::NotInitialized)?;
+    let bps = env
+        .storage()
+        .instance()
+        .get(&DataKey::FeeBps)
+        .ok_or(AuctionError::NotInitialized)?;
+    Ok((collector, bps))
+}
+
+/// The fee rate a sale at `price` pays: the tier it lands in when a schedule
+/// is configured (same last-entry-at-or-below semantics as the increment
+/// schedule), else the flat rate.
+fn fee_bps_for(env: &Env, flat_bps: u32, price: i128) -> u32 {
+    let schedule: Vec<(i128, u32)> = match env.storage().instance().get(&DataKey::FeeSchedule) {
+        Some(schedule) => schedule,
+        None => return flat_bps,
+    };
+    let mut bps = flat_bps;
+    for (threshold, tier_bps) in schedule.iter() {
+        if price >= threshold {
+            bps = tier_bps;
+        } else {
+            break;
+        }
+    }
+    bps
+}
+
+/// Record that `who` has had refund money actually delivered to them, for
+/// `was_refunded` support queries.
+fn mark_refunded(env: &Env, who: &Address) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::Refunded(who.clone()), &true);
+}
+
+/// Claw `amount` of `token` back out of `who`'s pending refunds, returning
+/// whether the full amount was available. Used when a parked refund is
+/// converted back into live escrow.
+fn take_pending(env: &Env, token: &Address, who: &Address, amount: i128) -> bool {
+    let key = DataKey::PendingRefund(who.clone());
+    let mut balances: Map<Address, i128> = match env.storage().persistent().get(&key) {
+        Some(balances) => balances,
+        None => return false,
+    };
+    let held = balances.get(token.clone()).unwrap_or(0);
+    if held < amount {
+        return false;
+    }
+    if held == amount {
+        balances.remove(token.clone());
+    } else {
+        balances.set(token.clone(), held - amount);
+    }
+    if balances.is_empty() {
+        env.storage().persistent().remove(&key);
+        let mut addrs: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::PendingAddrs)
+            .unwrap_or_else(|| Vec::new(env));
+        if let Some(pos) = addrs.first_index_of(who) {
+            addrs.remove(pos);
+            env.storage().persistent().set(&DataKey::PendingAddrs, &addrs);
+        }
+    } else {
+        env.storage().persistent().set(&key, &balances);
+    }
+    true
+}
+
+/// Credit a pull-mode refund to `to`, tracked per token.
+fn add_pending(env: &Env, token: &Address, to: &Address, amount: i128) {
+    if amount <= 0 {
+        return;
+    }
+    let key = DataKey::PendingRefund(to.clone());
+    let mut balances: Map<Address, i128> = env
+        .storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or_else(|| Map::new(env));
+    let held = balances.get(token.clone()).unwrap_or(0);
+    balances.set(token.clone(), held + amount);
+    env.storage().persistent().set(&key, &balances);
+
+    let mut addrs: Vec<Address> = env
+        .storage()
+        .persistent()
+        .get(&DataKey::PendingAddrs)
+        .unwrap_or_else(|| Vec::new(env));
+    if !addrs.contains(to) {
+        addrs.push_back(to.clone());
+        env.storage().persistent().set(&DataKey::PendingAddrs, &addrs);
+    }
+}
+
+/// Return escrowed bid-token funds to `to` following the auction's refund
+/// mode: immediate transfer (with a `refund` event) or pending-claim credit.
+/// `amount` is the net bid; any buyer premium escrowed with it rides along.
+fn refund_bidder(env: &Env, id: u64, auction: &Auction, to: &Address, amount: i128) {
+    if amount <= 0 {
+        return;
+    }
+    let amount = amount + bps_of(amount, auction.params.buyer_premium_bps);
+    match auction.params.refund_mode {
+        RefundMode::Immediate => {
+            // Defensive: by invariant the escrow covers every refund, but a
+            // misbehaving token (fee-on-transfer, clawback) can leave the
+            // contract short. Park the refund rather than bricking new bids.
+            let held =
+                token::Client::new(env, &auction.params.bid_token).balance(&env.current_contract_address());
+            if held >= amount
+                && escrow::try_release(env, &auction.params.bid_token, to, amount).is_ok()
+            {
+                env.events()
+                    .publish((symbol_short!("refund"), id), (to.clone(), amount));
+                mark_refunded(env, to);
+            } else {
+                // Undeliverable refunds park as pending claims rather than
+                // blocking the operation that triggered them.
+                add_pending(env, &auction.params.bid_token, to, amount);
+            }
+        }
+        RefundMode::Pull | RefundMode::Deferred => {
+            add_pending(env, &auction.params.bid_token, to, amount)
+        }
+    }
+}
+
+/// Opportunistically push out up to `limit` addresses' parked refunds.
+/// Called from hot paths under `RefundMode::Deferred`; a refund the
+/// recipient still rejects simply stays parked for next time.
+fn flush_deferred(env: &Env, limit: u32) {
+    let addrs: Vec<Address> = env
+        .storage()
+        .persistent()
+        .get(&DataKey::PendingAddrs)
+        .unwrap_or_else(|| Vec::new(env));
+    let mut flushed = 0u32;
+    for who in addrs.iter() {
+        if flushed >= limit {
+            break;
+        }
+        let key = DataKey::PendingRefund(who.clone());
+        let balances: Map<Address, i128> = match env.storage().persistent().get(&key) {
+            Some(balances) => balances,
+            None => continue,
+        };
+        // Effects before interactions: the entry and its address-list slot
+        // are cleared before any token call, so a token that re-enters
+        // `withdraw` from its transfer hook finds nothing left to take.
+        // Rejected amounts are re-credited afterwards.
+        env.storage().persistent().remove(&key);
+        let mut list: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::PendingAddrs)
+            .unwrap_or_else(|| Vec::new(env));
+        if let Some(pos) = list.first_index_of(&who) {
+            list.remove(pos);
+            env.storage().persistent().set(&DataKey::PendingAddrs, &list);
+        }
+        let mut delivered = false;
+        for (token_addr, amount) in balances.iter() {
+            if escrow::try_release(env, &token_addr, &who, amount).is_ok() {
+                delivered = true;
+            } else {
+                add_pending(env, &token_addr, &who, amount);
+            }
+        }
+        if delivered {
+            mark_refunded(env, &who);
+            flushed += 1;
+        }
+    }
+}
+
+/// Deliver value at settlement time. If the recipient can't receive the
+/// token (e.g. a missing trustline), the amount parks as a pending claim so
+/// settlement always finalizes instead of wedging the auction.
+fn settle_payout(env: &Env, token: &Address, to: &Address, amount: i128) {
+    if amount <= 0 {
+        return;
+    }
+    if escrow::try_release(env, token, to, amount).is_err() {
+        add_pending(env, token, to, amount);
+    }
+}
+
+/// Deliver the escrowed buyer premium on the final price to the fee
+/// collector. Kept out of `distribute` so the hammer-price splits (and the
+/// volume counter) never see premium money.
+fn pay_premium(env: &Env, auction: &Auction, price: i128) -> Result<(), AuctionError> {
+    let premium = bps_of(price, auction.params.buyer_premium_bps);
+    if premium > 0 {
+        let (collector, _) = fee_config(env)?;
+        settle_payout(env, &auction.params.bid_token, &collector, premium);
+    }
+    Ok(())
+}
+
+/// Split `amount` of sale proceeds between the fee collector, the royalty
+/// and donation recipients, and the seller, and roll it into the volume
+/// counter.
+fn distribute(env: &Env, id: u64, auction: &Auction, amount: i128) -> Result<(), AuctionError> {
+    if amount <= 0 {
+        return Ok(());
+    }
+    let total: i128 = env
+        .storage()
+        .instance()
+        .get(&DataKey::TotalVolume)
+        .unwrap_or(0);
+    let new_total = total.checked_add(amount).ok_or(AuctionError::MathOverflow)?;
+    env.storage().instance().set(&DataKey::TotalVolume, &new_total);
+    let (collector, flat_bps) = fee_config(env)?;
+    let fee = bps_of(amount, fee_bps_for(env, flat_bps, amount));
+    let royalty = bps_of(amount, auction.params.royalty.bps);
+    let donation = bps_of(amount, auction.params.donation.bps);
+    let burn = bps_of(amount, auction.params.burn_bps);
+    // Escrow-interest bonuses come out of the protocol fee, never the
+    // seller's share.
+    let collector_fee = pay_lock_bonuses(env, id, auction, fee);
+    settle_payout(env, &auction.params.bid_token, &collector, collector_fee);
+    if let Some(recipient) = &auction.params.royalty.recipient {
+        settle_payout(env, &auction.params.bid_token, recipient, royalty);
+    }
+    if let Some(recipient) = &auction.params.donation.recipient {
+        settle_payout(env, &auction.params.bid_token, recipient, donation);
+        env.events().publish(
+            (symbol_short!("donation"), id),
+            (recipient.clone(), donation),
+        );
+    }
+    if burn > 0 {
+        token::Client::new(env, &auction.params.bid_token)
+            .burn(&env.current_contract_address(), &burn);
+    }
+    let seller_cut = amount - fee - royalty - donation - burn;
+    if auction.params.co_sellers.is_empty() {
+        settle_payout(env, &auction.params.bid_token, &auction.params.seller, seller_cut);
+    } else {
+        // Each co-seller's share floors; the rounding dust lands on the last
+        // entry so the pieces always sum to the full seller cut.
+        let mut paid: i128 = 0;
+        let last = auction.params.co_sellers.len() - 1;
+        for (i, (who, share_bps)) in auction.params.co_sellers.iter().enumerate() {
+            let cut = if i as u32 == last {
+                seller_cut - paid
+            } else {
+                bps_of(seller_cut, share_bps)
+            };
+            settle_payout(env, &auction.params.bid_token, &who, cut);
+            paid += cut;
+        }
+    }
+    Ok(())
+}
+
+/// Release deposits at the end of an auction: losers are refunded, the
+/// winner's deposit (if any) is forfeited to the seller.
+fn release_deposits(env: &Env, id: u64, auction: &Auction, winner: Option<&Address>) {
+    let depositors: Vec<Address> = env
+        .storage()
+        .persistent()
+        .get(&DataKey::Depositors(id))
+        .unwrap_or_else(|| Vec::new(env));
+    for who in depositors.iter() {
+        let key = DataKey::Deposit(id, who.clone());
+        let amount: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+        env.storage().persistent().remove(&key);
+        if Some(&who) == winner {
+            // Parking, not trapping: a seller who cannot receive must not
+            // make `settle` revert and strand everyone else's deposits.
+            settle_payout(env, &auction.params.bid_token, &auction.params.seller, amount);
+            if amount > 0 {
+                env.storage()
+                    .persistent()
+                    .set(&DataKey::ForfeitedDeposit(id), &amount);
+            }
+        } else {
+            refund_bidder(env, id, auction, &who, amount);
+        }
+    }
+    env.storage().persistent().remove(&DataKey::Depositors(id));
+}
+
+/// Credit a displaced leader with the token-seconds their escrow spent
+/// locked, for the escrow-interest bonus. A no-op while no bonus rate is
+/// configured, so ordinary deployments pay no storage cost.
+fn accrue_lock(env: &Env, id: u64, who: &Address, amount: i128, since: u64) {
+    if !env.storage().instance().has(&DataKey::BonusRate) || amount <= 0 {
+        return;
+    }
+    let held = i128::from(env.ledger().timestamp().saturating_sub(since));
+    if held <= 0 {
+        return;
+    }
+    let key = DataKey::LockAccrual(id, who.clone());
+    let prior: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+    env.storage()
+        .persistent()
+        .set(&key, &(prior + amount.saturating_mul(held)));
+}
+
+/// Pay the escrow-interest bonus to every non-winning bidder out of `fee`,
+/// returning how much of the fee is left for the collector. Bonuses are
+/// proportional to accrued token-seconds and scale down together when they
+/// would exceed the fee, so the pool can never go insolvent.
+fn pay_lock_bonuses(env: &Env, id: u64, auction: &Auction, fee: i128) -> i128 {
+    let rate: u32 = match env.storage().instance().get(&DataKey::BonusRate) {
+        Some(rate) => rate,
+        None => return fee,
+    };
+    let bidders: Vec<Address> = env
+        .storage()
+        .persistent()
+        .get(&DataKey::Bidders(id))
+        .unwrap_or_else(|| Vec::new(env));
+    let mut owed: Vec<(Address, i128)> = Vec::new(env);
+    let mut owed_total: i128 = 0;
+    for who in bidders.iter() {
+        let key = DataKey::LockAccrual(id, who.clone());
+        let accrual: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+        env.storage().persistent().remove(&key);
+        if auction.highest_bidder.as_ref() == Some(&who) {
+            continue;
+        }
+        let bonus = accrual / 86_400 * i128::from(rate) / 10_000;
+        if bonus > 0 {
+            owed.push_back((who, bonus));
+            owed_total += bonus;
+        }
+    }
+    if owed_total == 0 || fee <= 0 {
+        return fee;
+    }
+    let mut paid_total: i128 = 0;
+    for (who, bonus) in owed.iter() {
+        let paid = if owed_total > fee {
+            bonus * fee / owed_total
+        } else {
+            bonus
+        };
+        settle_payout(env, &auction.params.bid_token, &who, paid);
+        paid_total += paid;
+    }
+    fee - paid_total
+}
+
+/// Resolve an auction's escrowed listing fee at close-out: back to the
+/// seller if enough distinct bidders showed up, otherwise to the fee
+/// collector. A no-op for auctions created while no policy was set.
+fn settle_listing_fee(env: &Env, id: u64, auction: &Auction) {
+    let key = DataKey::ListingFee(id);
+    let escrowed: Option<(i128, u32)> = env.storage().persistent().get(&key);
+    if let Some((fee, min_bidders)) = escrowed {
+        env.storage().persistent().remove(&key);
+        if auction.distinct_bidders >= min_bidders {
+            settle_payout(env, &auction.params.bid_token, &auction.params.seller, fee);
+        } else if let Ok((collector, _)) = fee_config(env) {
+            settle_payout(env, &auction.params.bid_token, &collector, fee);
+        }
+    }
+}
+
+/// The increment a new bid must clear on top of `current`: the stepped
+/// schedule when one is configured, else the flat `min_increment`.
+fn required_increment(params: &AuctionParams, current: i128) -> i128 {
+    let mut increment = params.min_increment;
+    for (threshold, step) in params.increment_schedule.iter() {
+        if current >= threshold {
+            increment = step;
+        } else {
+            break;
+        }
+    }
+    increment
+}
+
+/// Hand the lot to `to`: a lazy-mint auction mints it on the spot, anything
+/// else releases the escrowed asset (parking it as a pending claim if the
+/// transfer fails).
+fn deliver_asset(env: &Env, auction: &Auction, to: &Address, amount: i128) {
+    if auction.params.mint_on_settle {
+        let () = env.invoke_contract(
+            &auction.params.asset,
+            &Symbol::new(env, "mint"),
+            soroban_sdk::vec![env, to.into_val(env), amount.into_val(env)],
+        );
+    } else {
+        settle_payout(env, &auction.params.asset, to, amount);
+    }
+}
+
+/// Pull `amount` of the primary asset back from an external custodian ahead
+/// of delivery; a no-op for self-custodied auctions. A custodian that fails
+/// to deliver leaves the contract short, which the caller's asset-missing
+/// handling picks up.
+fn recall_asset(env: &Env, auction: &Auction, amount: i128) {
+    if amount <= 0 {
+        return;
+    }
+    if let Some(custodian) = &auction.params.escrow_address {
+        let _ = token::Client::new(env, &auction.params.asset).try_transfer_from(
+            &env.current_contract_address(),
+            custodian,
+            &env.current_contract_address(),
+            &amount,
+        );
+    }
+}
+
+/// Deliver every extra bundle lot alongside the primary asset.
+fn move_bundle(env: &Env, auction: &Auction, to: &Address) {
+    for (item_token, item_amount) in auction.params.bundle.iter() {
+        settle_payout(env, &item_token, to, item_amount);
+    }
+}
+
+/// Current Dutch price: linear decay from `buy_now_price` at the start down
+/// to `reserve_price` at the end.
+fn dutch_price(auction: &Auction, now: u64) -> i128 {
+    let start_price = auction.params.buy_now_price;
+    let floor = auction.params.reserve_price;
+    if now <= auction.start_time {
+        return start_price;
+    }
+    if now >= auction.end_time {
+        return floor;
+    }
+    let elapsed = i128::from(now - auction.start_time);
+    if auction.params.dutch_step.interval > 0 {
+        // Discrete decay: the price holds within each interval and drops by
+        // the step amount at every boundary, never below the floor.
+        let steps = elapsed / i128::from(auction.params.dutch_step.interval);
+        return (start_price - steps * auction.params.dutch_step.amount).max(floor);
+    }
+    let window = i128::from(auction.end_time - auction.start_time);
+    start_price - (start_price - floor) * elapsed / window
+}
+
+fn validate_params(env: &Env, params: &AuctionParams) -> Result<(), AuctionError> {
+    let (_, fee_bps) = fee_config(env)?;
+    if params.asset_amount <= 0
+        || params.reserve_price <= 0
+        || params.reserve_ratio < 0
+        || params.duration == 0
+        || params.min_increment < 0
+        || params.bid_tick < 0
+        || params.deposit < 0
+        || params.minimum_escalation < 0
+        || params.retraction_penalty < 0
+    {
+        return Err(AuctionError::InvalidParams);
+    }
+    if params.reserve_ratio > 0
+        && params
+            .asset_amount
+            .checked_mul(params.reserve_ratio)
+            .is_none()
+    {
+        return Err(AuctionError::MathOverflow);
+    }
+    if params.twap.oracle.is_some() && params.twap.reserve <= 0 {
+        return Err(AuctionError::InvalidParams);
+    }
+    if params.max_escrow.is_some_and(|cap| cap <= 0) {
+        return Err(AuctionError::InvalidParams);
+    }
+    if fee_bps + params.royalty.bps + params.donation.bps + params.burn_bps > 10_000 {
+        return Err(AuctionError::InvalidParams);
+    }
+    for share in [&params.royalty, &params.donation] {
+        if share.bps > 0 && share.recipient.is_none() {
+            return Err(AuctionError::InvalidParams);
+        }
+    }
+    if params.dutch_step.interval > 0
+        && (params.kind != AuctionKind::Dutch || params.dutch_step.amount <= 0)
+    {
+        return Err(AuctionError::InvalidParams);
+    }
+    if params.installments.deadline > 0
+        && (params.kind != AuctionKind::English
+            || params.installments.down_bps > 10_000
+            || params.installments.refund_bps > 10_000
+            || params.buyer_premium_bps > 0
+            || params.mint_on_settle)
+    {
+        return Err(AuctionError::InvalidParams);
+    }
+    if !params.co_sellers.is_empty() {
+        let mut share_total: u32 = 0;
+        for (_, share_bps) in params.co_sellers.iter() {
+            if share_bps == 0 || share_bps > 10_000 {
+                return Err(AuctionError::InvalidParams);
+            }
+            share_total += share_bps;
+        }
+        if share_total != 10_000 {
+            return Err(AuctionError::InvalidParams);
+        }
+    }
+    if !params.bundle.is_empty() && params.kind == AuctionKind::Divisible {
+        return Err(AuctionError::InvalidParams);
+    }
+    if params.mint_on_settle
+        && (params.kind != AuctionKind::English
+            || !params.bundle.is_empty()
+            || params.escrow_address.is_some())
+    {
+        return Err(AuctionError::InvalidParams);
+    }
+    let mut last_threshold: Option<i128> = None;
+    for (threshold, step) in params.increment_schedule.iter() {
+        if step <= 0 || last_threshold.is_some_and(|prev| threshold <= prev) {
+            return Err(AuctionError::InvalidParams);
+        }
+        last_threshold = Some(threshold);
+    }
+    for (i, (item_token, item_amount)) in params.bundle.iter().enumerate() {
+        if item_amount <= 0 || item_token == params.asset {
+            return Err(AuctionError::DuplicateAsset);
+        }
+        for (other_token, _) in params.bundle.iter().take(i) {
+            if item_token == other_token {
+                return Err(AuctionError::DuplicateAsset);
+            }
+        }
+    }
+    match params.kind {
+        AuctionKind::Dutch => {
+            // The decay needs a starting price strictly above the floor.
+            if params.buy_now_price <= params.reserve_price {
+                return Err(AuctionError::InvalidParams);
+            }
+        }
+        _ => {
+            if params.buy_now_price != 0 && params.buy_now_price < params.reserve_price {
+                return Err(AuctionError::InvalidParams);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Shared eligibility checks for bid-like entry points.
+fn check_bid_window(env: &Env, auction: &Auction, who: &Address) -> Result<(), AuctionError> {
+    let now = env.ledger().timestamp();
+    if now < auction.start_time {
+        return Err(AuctionError::NotStarted);
+    }
+    if now >= auction.end_time {
+        return Err(AuctionError::AuctionEnded);
+    }
+    if auction.seller_paused_at > 0 {
+        return Err(AuctionError::SellerPaused);
+    }
+    if !auction.params.whitelist.is_empty() && !auction.params.whitelist.contains(who) {
+        return Err(AuctionError::NotWhitelisted);
+    }
+    // The attestation is queried live on every bid — nothing is cached, so a
+    // revoked attestation takes effect on the very next call.
+    if let Some(kyc) = env
+        .storage()
+        .instance()
+        .get::<_, Address>(&DataKey::KycContract)
+    {
+        let verified: bool = env.invoke_contract(
+            &kyc,
+            &Symbol::new(env, "is_verified"),
+            soroban_sdk::vec![env, who.into_val(env)],
+        );
+        if !verified {
+            return Err(AuctionError::NotVerified);
+        }
+    }
+    Ok(())
+}
+
+/// The minimum an ascending-format bid must reach right now: reserve plus
+/// opening premium for the first bid, increment rules afterwards, and never
+/// below a configured TWAP floor.
+fn bid_minimum(env: &Env, auction: &Auction) -> Result<i128, AuctionError> {
+    let minimum = match auction.highest_bidder {
+        None => {
+            let reserve = effective_reserve(&auction.params);
+            reserve + bps_of(reserve, auction.params.opening_bid_bps)
+        }
+        Some(_) => {
+            // Every distinct bidder past the first raises the bar by the
+            // configured escalation on top of the increment rules.
+            let escalation = auction.params.minimum_escalation
+                * i128::from(auction.distinct_bidders.saturating_sub(1));
+            auction.highest_bid
+                + required_increment(&auction.params, auction.highest_bid)
+                + escalation
+        }
+    };
+    Ok(minimum.max(twap_floor(env, &auction.params)?))
+}
+
+/// The cap a descending (reverse) ask must stay at or below right now.
+fn reverse_maximum(auction: &Auction) -> i128 {
+    match auction.highest_bidder {
+        None => effective_reserve(&auction.params),
+        Some(_) => auction.highest_bid - required_increment(&auction.params, auction.highest_bid),
+    }
+}
+
+/// Reject a bid that would push the auction's held bid token past its
+/// configured `max_escrow`. The projection is the would-be leading escrow
+/// (bid plus premium), and for `SplitTopTwo` the runner-up escrow that stays
+/// locked alongside it.
+fn check_escrow_cap(auction: &Auction, amount: i128) -> Result<(), AuctionError> {
+    let cap = match auction.params.max_escrow {
+        Some(cap) => cap,
+        None => return Ok(()),
+    };
+    let mut projected = amount + bps_of(amount, auction.params.buyer_premium_bps);
+    if auction.params.kind == AuctionKind::SplitTopTwo && auction.highest_bidder.is_some() {
+        let prev = auction.highest_bid;
+        projected += prev + bps_of(prev, auction.params.buyer_premium_bps);
+    }
+    if projected > cap {
+        return Err(AuctionError::EscrowCapExceeded);
+    }
+    Ok(())
+}
+
+/// One whole token of the bid asset in stroops, per its `decimals()`.
+fn whole_unit(env: &Env, params: &AuctionParams) -> i128 {
+    let decimals = token::Client::new(env, &params.bid_token).decimals();
+    10i128.pow(decimals)
+}
+
+/// The good-faith deposit an address has posted on an auction so far.
+fn deposit_of(env: &Env, id: u64, who: &Address) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Deposit(id, who.clone()))
+        .unwrap_or(0)
+}
+
+/// Whether `bidder`'s bid of `amount`, short of the regular minimum, still
+/// takes the lead under the auction's tie-break rule: it must exactly match
+/// the leader's bid and be backed by a strictly larger deposit.
+fn wins_tie(env: &Env, id: u64, auction: &Auction, bidder: &Address, amount: i128) -> bool {
+    if auction.params.tie_break != TieBreak::HighestDeposit {
+        return false;
+    }
+    let leader = match &auction.highest_bidder {
+        Some(leader) => leader,
+        None => return false,
+    };
+    amount == auction.highest_bid
+        && deposit_of(env, id, bidder) > deposit_of(env, id, leader)
+}
+
+/// Count `bidder` towards the auction's distinct-bidder tally on their
+/// first accepted bid.
+fn record_bidder(env: &Env, id: u64, auction: &mut Auction, bidder: &Address) {
+    let key = DataKey::Bidders(id);
+    let mut bidders: Vec<Address> = env
+        .storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or_else(|| Vec::new(env));
+    if !bidders.contains(bidder) {
+        bidders.push_back(bidder.clone());
+        env.storage().persistent().set(&key, &bidders);
+        auction.distinct_bidders = bidders.len();
+        let total: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::TotalBidders)
+            .unwrap_or(0);
+        env.storage()
+            .instance()
+            .set(&DataKey::TotalBidders, &(total + 1));
+    }
+}
+
+/// Take the per-bidder good-faith deposit on an address's first bid.
+fn collect_deposit(env: &Env, id: u64, auction: &Auction, bidder: &Address) {
+    if auction.params.deposit <= 0 {
+        return;
+    }
+    let key = DataKey::Deposit(id, bidder.clone());
+    if env.storage().persistent().has(&key) {
+        return;
+    }
+    escrow::lock(env, &auction.params.bid_token, bidder, auction.params.deposit);
+    env.storage().persistent().set(&key, &auction.params.deposit);
+    let mut depositors: Vec<Address> = env
+        .storage()
+        .persistent()
+        .get(&DataKey::Depositors(id))
+        .unwrap_or_else(|| Vec::new(env));
+    depositors.push_back(bidder.clone());
+    env.storage()
+        .persistent()
+        .set(&DataKey::Depositors(id), &depositors);
+}
+
+#[contract]
+pub struct AuctionContract;
+
+#[contractimpl]
+impl AuctionContract {
+    /// One-time setup of the admin and the protocol fee taken from proceeds.
+    pub fn initialize(
+        env: Env,
+        admin: Address,
+        fee_collector: Address,
+        fee_bps: u32,
+    ) -> Result<(), AuctionError> {
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(AuctionError::AlreadyInitialized);
+        }
+        if fee_bps > 10_000 {
+            return Err(AuctionError::InvalidParams);
+        }
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage()
+            .instance()
+            .set(&DataKey::FeeCollector, &fee_collector);
+        env.storage().instance().set(&DataKey::FeeBps, &fee_bps);
+        env.storage().instance().set(&DataKey::Counter, &0u64);
+        Ok(())
+    }
+
+    /// Admin cap on how many auctions may be active at once, to bound
+    /// storage growth on shared deployments. Settling frees a slot.
+    pub fn set_max_active_auctions(env: Env, max: u32) -> Result<(), AuctionError> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(AuctionError::NotInitialized)?;
+        admin.require_auth();
+        env.storage().instance().set(&DataKey::MaxActive, &max);
+        Ok(())
+    }
+
+    /// Admin circuit breaker: blocks creation and bidding, never settlement
+    /// or withdrawals.
+    pub fn set_paused(env: Env, paused: bool) -> Result<(), AuctionError> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(AuctionError::NotInitialized)?;
+        admin.require_auth();
+        env.storage().instance().set(&DataKey::Paused, &paused);
+        Ok(())
+    }
+
+    /// Install a tiered fee schedule of ascending `(price threshold, bps)`
+    /// pairs — the tier a final price lands in replaces the flat fee — or
+    /// clear it with an empty vector.
+    pub fn set_fee_schedule(
+        env: Env,
+        schedule: Vec<(i128, u32)>,
+    ) -> Result<(), AuctionError> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(AuctionError::NotInitialized)?;
+        admin.require_auth();
+        let mut last_threshold: Option<i128> = None;
+        for (threshold, bps) in schedule.iter() {
+            if bps > 10_000 || last_threshold.is_some_and(|prev| threshold <= prev) {
+                return Err(AuctionError::InvalidParams);
+            }
+            last_threshold = Some(threshold);
+        }
+        if schedule.is_empty() {
+            env.storage().instance().remove(&DataKey::FeeSchedule);
+        } else {
+            env.storage().instance().set(&DataKey::FeeSchedule, &schedule);
+        }
+        Ok(())
+    }
+
+    /// Charge sellers `fee` (in their auction's bid token) up front; it is
+    /// returned at settlement if at least `min_bidders` distinct addresses
+    /// bid, and kept by the fee collector otherwise. A zero fee clears the
+    /// policy. Auctions snapshot the policy at creation.
+    pub fn set_listing_fee(
+        env: Env,
+        fee: i128,
+        min_bidders: u32,
+    ) -> Result<(), AuctionError> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(AuctionError::NotInitialized)?;
+        admin.require_auth();
+        if fee < 0 {
+            return Err(AuctionError::InvalidParams);
+        }
+        if fee == 0 {
+            env.storage().instance().remove(&DataKey::ListingFeeConfig);
+        } else {
+            env.storage()
+                .instance()
+                .set(&DataKey::ListingFeeConfig, &(fee, min_bidders));
+        }
+        Ok(())
+    }
+
+    /// Make sellers wait `seconds` after cancelling an auction before they
+    /// can create another, damping list/cancel visibility games. 0 clears
+    /// the policy.
+    pub fn set_relist_cooldown(env: Env, seconds: u64) -> Result<(), AuctionError> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(AuctionError::NotInitialized)?;
+        admin.require_auth();
+        if seconds == 0 {
+            env.storage().instance().remove(&DataKey::RelistCooldown);
+        } else {
+            env.storage().instance().set(&DataKey::RelistCooldown, &seconds);
+        }
+        Ok(())
+    }
+
+    /// Restrict future auctions to being denominated in one of `tokens`; an
+    /// empty vector lifts the restriction. Running auctions keep the token
+    /// they were created with. A deploying factory installs its own allowed
+    /// set here so the restriction survives into the instance.
+    pub fn set_allowed_bid_tokens(env: Env, tokens: Vec<Address>) -> Result<(), AuctionError> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(AuctionError::NotInitialized)?;
+        admin.require_auth();
+        if tokens.is_empty() {
+            env.storage().instance().remove(&DataKey::AllowedBidTokens);
+        } else {
+            env.storage()
+                .instance()
+                .set(&DataKey::AllowedBidTokens, &tokens);
+        }
+        Ok(())
+    }
+
+    /// The installed bid-token restriction; empty means unrestricted.
+    pub fn get_allowed_bid_tokens(env: Env) -> Vec<Address> {
+        env.storage()
+            .instance()
+            .get(&DataKey::AllowedBidTokens)
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    /// Pay displaced bidders interest on the time their escrow spent locked,
+    /// at `bps_per_day` of the locked amount, funded out of (and capped by)
+    /// the protocol fee at settlement. 0 clears the policy.
+    pub fn set_escrow_bonus(env: Env, bps_per_day: u32) -> Result<(), AuctionError> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(AuctionError::NotInitialized)?;
+        admin.require_auth();
+        if bps_per_day > 10_000 {
+            return Err(AuctionError::InvalidParams);
+        }
+        if bps_per_day == 0 {
+            env.storage().instance().remove(&DataKey::BonusRate);
+        } else {
+            env.storage().instance().set(&DataKey::BonusRate, &bps_per_day);
+        }
+        Ok(())
+    }
+
+    /// Point bidding at a KYC attestation contract exposing
+    /// `is_verified(who: Address) -> bool`, or clear the requirement.
+    pub fn set_kyc_contract(env: Env, kyc: Option<Address>) -> Result<(), AuctionError> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(AuctionError::NotInitialized)?;
+        admin.require_auth();
+        match kyc {
+            Some(addr) => env.storage().instance().set(&DataKey::KycContract, &addr),
+            None => env.storage().instance().remove(&DataKey::KycContract),
+        }
+        Ok(())
+    }
+
+    /// One-time upgrade hook for contracts deployed before the multi-auction
+    /// refactor: rewrites the legacy un-keyed auction state under id `0` so
+    /// the id-keyed API can read it, then locks itself out.
+    pub fn migrate(env: Env) -> Result<(), AuctionError> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(AuctionError::NotInitialized)?;
+        admin.require_auth();
+        if env.storage().instance().has(&DataKey::Migrated) {
+            return Err(AuctionError::AlreadyInitialized);
+        }
+        if let Some(auction) = env
+            .storage()
+            .persistent()
+            .get::<_, Auction>(&LegacyKey::Auction)
+        {
+            let is_settled: bool = env
+                .storage()
+                .persistent()
+                .get(&LegacyKey::IsSettled)
+                .unwrap_or(false);
+            write_auction(&env, 0, &auction);
+            if is_settled {
+                env.storage().persistent().set(&DataKey::IsSettle(0), &true);
+            } else {
+                let active: u32 = env
+                    .storage()
+                    .instance()
+                    .get(&DataKey::ActiveCount)
+                    .unwrap_or(0);
+                env.storage().instance().set(&DataKey::ActiveCount, &(active + 1));
+            }
+            let mut ids: Vec<u64> = env
+                .storage()
+                .persistent()
+                .get(&DataKey::SellerAuctions(auction.params.seller.clone()))
+                .unwrap_or_else(|| Vec::new(&env));
+            ids.push_front(0);
+            env.storage()
+                .persistent()
+                .set(&DataKey::SellerAuctions(auction.params.seller), &ids);
+            let counter: u64 = env
+                .storage()
+                .instance()
+                .get(&DataKey::Counter)
+                .unwrap_or(0);
+            env.storage()
+                .instance()
+                .set(&DataKey::Counter, &counter.max(1));
+            env.storage().persistent().remove(&LegacyKey::Auction);
+            env.storage().persistent().remove(&LegacyKey::IsSettled);
+        }
+        env.storage().instance().set(&DataKey::Migrated, &true);
+        Ok(())
+    }
+
+    /// Escrow the seller's asset and open a new auction, returning its id.
+    pub fn create_auction(env: Env, params: AuctionParams) -> Result<u64, AuctionError> {
+        Self::create_internal(env, params, false)
+    }
+
+    /// Like `create_auction`, but pulls the asset via a pre-approved
+    /// allowance (`transfer_from` with this contract as spender), so the
+    /// approval and the creation fit in one transaction envelope.
+    pub fn create_with_allowance(env: Env, params: AuctionParams) -> Result<u64, AuctionError> {
+        Self::create_internal(env, params, true)
+    }
+
+    /// Like `create_auction`, but consumes a client-supplied idempotency
+    /// nonce so a retried transaction cannot open a duplicate auction: the
+    /// second submission fails with `NonceUsed` instead of creating again.
+    pub fn create_with_nonce(
+        env: Env,
+        params: AuctionParams,
+        nonce: u64,
+    ) -> Result<u64, AuctionError> {
+        let key = DataKey::UsedNonce(params.seller.clone(), nonce);
+        if env.storage().persistent().has(&key) {
+            return Err(AuctionError::NonceUsed);
+        }
+        let id = Self::create_internal(env.clone(), params, false)?;
+        env.storage().persistent().set(&key, &id);
+        Ok(id)
+    }
+
+    fn create_internal(
+        env: Env,
+        params: AuctionParams,
+        use_allowance: bool,
+    ) -> Result<u64, AuctionError> {
+        require_not_paused(&env)?;
+        params.seller.require_auth();
+        validate_params(&env, &params)?;
+        if let Some(allowed) = env
+            .storage()
+            .instance()
+            .get::<_, Vec<Address>>(&DataKey::AllowedBidTokens)
+        {
+            if !allowed.contains(&params.bid_token) {
+                return Err(AuctionError::BidTokenNotAllowed);
+            }
+        }
+        if let Some(cooldown) = env
+            .storage()
+            .instance()
+            .get::<_, u64>(&DataKey::RelistCooldown)
+        {
+            let last_cancel_key = DataKey::LastCancel(params.seller.clone());
+            if let Some(last) = env.storage().persistent().get::<_, u64>(&last_cancel_key) {
+                if env.ledger().timestamp() < compute_end_time(last, cooldown)? {
+                    return Err(AuctionError::CooldownActive);
+                }
+            }
+        }
+        let active: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::ActiveCount)
+            .unwrap_or(0);
+        if let Some(max) = env.storage().instance().get::<_, u32>(&DataKey::MaxActive) {
+            if active >= max {
+                return Err(AuctionError::TooManyActiveAuctions);
+            }
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::ActiveCount, &(active + 1));
+
+        let now = env.ledger().timestamp();
+        let start_time = if params.start_time == 0 {
+            now
+        } else {
+            params.start_time
+        };
+        if start_time < now {
+            return Err(AuctionError::InvalidParams);
+        }
+        let end_time = compute_end_time(start_time, params.duration)?;
+        // Defensive: scheduling math is checked above, but a window that has
+        // already closed must never be accepted.
+        if end_time <= now {
+            return Err(AuctionError::EndInPast);
+        }
+
+        let id: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::Counter)
+            .ok_or(AuctionError::NotInitialized)?;
+        env.storage().instance().set(&DataKey::Counter, &(id + 1));
+
+        if params.mint_on_settle {
+            // Lazy mint: nothing exists to escrow; the NFT is minted
+            // straight to the winner at settlement.
+        } else if use_allowance {
+            escrow::lock_from(&env, &params.asset, &params.seller, params.asset_amount);
+        } else {
+            escrow::lock(&env, &params.asset, &params.seller, params.asset_amount);
+        }
+        if let Some(custodian) = &params.escrow_address {
+            // External custody: forward the just-locked asset on to the
+            // custodian; it comes back via `recall_asset` at delivery time.
+            escrow::release(&env, &params.asset, custodian, params.asset_amount);
+        }
+        if params.kind == AuctionKind::Reverse {
+            // The payment budget the winning taker is paid from.
+            escrow::lock(&env, &params.bid_token, &params.seller, params.reserve_price);
+        }
+        for (item_token, item_amount) in params.bundle.iter() {
+            escrow::lock(&env, &item_token, &params.seller, item_amount);
+        }
+        if let Some((fee, min_bidders)) = env
+            .storage()
+            .instance()
+            .get::<_, (i128, u32)>(&DataKey::ListingFeeConfig)
+        {
+            escrow::lock(&env, &params.bid_token, &params.seller, fee);
+            env.storage()
+                .persistent()
+                .set(&DataKey::ListingFee(id), &(fee, min_bidders));
+        }
+
+        let mut auction = Auction {
+            params: params.clone(),
+            start_time,
+            end_time: 0,
+            highest_bidder: None,
+            highest_bid: 0,
+            refund_to: None,
+            second_bidder: None,
+            second_bid: 0,
+            last_bid_time: 0,
+            settled_at: 0,
+            remaining: params.asset_amount,
+            proceeds: 0,
+            relists_done: 0,
+            seller_paused_at: 0,
+            distinct_bidders: 0,
+        };
+        apply_end_time(&mut auction, end_time)?;
+        write_auction(&env, id, &auction);
+        let seller_key = DataKey::SellerAuctions(params.seller.clone());
+        let mut seller_ids: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&seller_key)
+            .unwrap_or_else(|| Vec::new(&env));
+        seller_ids.push_back(id);
+        env.storage().persistent().set(&seller_key, &seller_ids);
+        if params.category != Symbol::new(&env, "") {
+            let category_key = DataKey::Category(params.category.clone());
+            let mut category_ids: Vec<u64> = env
+                .storage()
+                .persistent()
+                .get(&category_key)
+                .unwrap_or_else(|| Vec::new(&env));
+            category_ids.push_back(id);
+            env.storage().persistent().set(&category_key, &category_ids);
+        }
+        env.events()
+            .publish((symbol_short!("created"), id), params.seller);
+        Ok(id)
+    }
+
+    /// Place or raise a bid on an English or Vickrey auction. The full bid
+    /// amount is escrowed; the previously leading bidder is refunded. An
+    /// optional `refund_to` routes this bidder's eventual refund elsewhere
+    /// (e.g. a custody wallet).
+    pub fn place_bid(
+        env: Env,
+        id: u64,
+        bidder: Address,
+        amount: i128,
+        refund_to: Option<Address>,
+    ) -> Result<(), AuctionError> {
+        require_not_paused(&env)?;
+        bidder.require_auth();
+        Self::place_bid_internal(env, id, bidder, amount, refund_to)
+    }
+
+    /// The bid placement itself, shared with `increase_bid`. Callers have
+    /// already authenticated `bidder` and checked the pause switch.
+    fn place_bid_internal(
+        env: Env,
+        id: u64,
+        bidder: Address,
+        amount: i128,
+        refund_to: Option<Address>,
+    ) -> Result<(), AuctionError> {
+        let mut auction = read_auction(&env, id)?;
+        if settled(&env, id) {
+            return Err(AuctionError::AlreadySettled);
+        }
+        match auction.params.kind {
+            AuctionKind::English
+            | AuctionKind::Vickrey
+            | AuctionKind::SplitTopTwo
+            | AuctionKind::Reverse => {}
+            _ => return Err(AuctionError::WrongKind),
+        }
+        check_bid_window(&env, &auction, &bidder)?;
+        // Reverse auctions invert everything: asks descend, nothing is
+        // escrowed from bidders, and the best (lowest) ask simply takes the
+        // lead.
+        if auction.params.kind == AuctionKind::Reverse {
+            let maximum = reverse_maximum(&auction);
+            if amount < 0 || amount > maximum {
+                return Err(AuctionError::BidTooLow);
+            }
+            record_bidder(&env, id, &mut auction, &bidder);
+            auction.highest_bidder = Some(bidder.clone());
+            auction.highest_bid = amount;
+            let now = env.ledger().timestamp();
+            auction.last_bid_time = now;
+            if auction.params.anti_snipe.window > 0
+                && auction.end_time - now <= auction.params.anti_snipe.window
+            {
+                let end_time =
+                    compute_end_time(auction.end_time, auction.params.anti_snipe.period)?;
+                apply_end_time(&mut auction, end_time)?;
+            }
+            write_auction(&env, id, &auction);
+            env.events()
+                .publish((symbol_short!("bid"), id), (bidder, amount, true, 1u32));
+            return Ok(());
+        }
+
+        // Buy-now precedence: a bid that reaches a configured buy-now price
+        // (which may equal the reserve) is a purchase, not merely the lead —
+        // the auction ends on the spot.
+        if auction.params.kind == AuctionKind::English
+            && auction.params.buy_now_price > 0
+            && amount >= auction.params.buy_now_price
+        {
+            return Self::buy_now_internal(env, id, bidder, amount);
+        }
+        if auction.params.bid_tick > 0 && amount % auction.params.bid_tick != 0 {
+            return Err(AuctionError::BidNotOnTick);
+        }
+        if auction.params.whole_units_only && amount % whole_unit(&env, &auction.params) != 0 {
+            return Err(AuctionError::BidNotOnTick);
+        }
+        let minimum = bid_minimum(&env, &auction)?;
+        let tie_win = wins_tie(&env, id, &auction, &bidder, amount);
+        if amount < minimum && !tie_win {
+            return Err(AuctionError::BidTooLow);
+        }
+        check_escrow_cap(&auction, amount)?;
+
+        // A leading bidder raising their own bid only moves the delta;
+        // refunding them just to re-collect the full amount wastes a transfer
+        // round-trip and emits a misleading refund event.
+        let self_raise = auction.highest_bidder.as_ref() == Some(&bidder);
+        let net_amount = if self_raise {
+            amount - auction.highest_bid
+        } else {
+            amount
+        };
+        // The buyer premium is escrowed together with the bid itself.
+        let transfer_amount = net_amount + bps_of(amount, auction.params.buyer_premium_bps)
+            - if self_raise {
+                bps_of(auction.highest_bid, auction.params.buyer_premium_bps)
+            } else {
+                0
+            };
+        // Fee-on-transfer tokens deliver less than the sent amount, so the
+        // effective bid is whatever actually arrived in escrow; a bid the fee
+        // drags below the threshold is rejected (rolling the transfer back).
+        let bid_client = token::Client::new(&env, &auction.params.bid_token);
+        let held_before = bid_client.balance(&env.current_contract_address());
+        escrow::lock(&env, &auction.params.bid_token, &bidder, transfer_amount);
+        let received = bid_client.balance(&env.current_contract_address()) - held_before;
+        // A token that delivered everything recorded the intended net bid; a
+        // fee-on-transfer shortfall scales the net back out of what arrived.
+        let net_received = if received == transfer_amount {
+            net_amount
+        } else {
+            received * 10_000 / (10_000 + i128::from(auction.params.buyer_premium_bps))
+        };
+        let effective = if self_raise {
+            auction.highest_bid + net_received
+        } else {
+            net_received
+        };
+        if effective < minimum && !(tie_win && effective >= auction.highest_bid) {
+            return Err(AuctionError::BidTooLow);
+        }
+        collect_deposit(&env, id, &auction, &bidder);
+        record_bidder(&env, id, &mut auction, &bidder);
+
+        if !self_raise {
+            if let Some(prev) = auction.highest_bidder.clone() {
+                if auction.params.kind == AuctionKind::SplitTopTwo {
+                    // The outgoing leader keeps their escrow in place — they
+                    // are now the funded runner-up. Only the displaced second
+                    // place is refunded.
+                    if let Some(old_second) = auction.second_bidder.clone() {
+                        refund_bidder(&env, id, &auction, &old_second, auction.second_bid);
+                    }
+                } else {
+                    accrue_lock(&env, id, &prev, auction.highest_bid, auction.last_bid_time);
+                    let dest = auction.refund_to.clone().unwrap_or_else(|| prev.clone());
+                    refund_bidder(&env, id, &auction, &dest, auction.highest_bid);
+                }
+                auction.second_bidder = Some(prev);
+                auction.second_bid = auction.highest_bid;
+            }
+        }
+        auction.highest_bidder = Some(bidder.clone());
+        auction.highest_bid = effective;
+        auction.refund_to = refund_to;
+        let now = env.ledger().timestamp();
+        auction.last_bid_time = now;
+
+        // Anti-snipe: a bid inside the closing window pushes the end out —
+        // unless the auction only extends for bids that clear the soft
+        // reserve and this one doesn't.
+        let extension_worthy = !auction.params.anti_snipe.above_reserve_only
+            || effective >= auction.params.soft_reserve.max(effective_reserve(&auction.params));
+        if auction.params.anti_snipe.window > 0
+            && extension_worthy
+            && auction.end_time - now <= auction.params.anti_snipe.window
+        {
+            let end_time = compute_end_time(auction.end_time, auction.params.anti_snipe.period)?;
+            apply_end_time(&mut auction, end_time)?;
+        }
+
+        write_auction(&env, id, &auction);
+        if auction.params.refund_mode == RefundMode::Deferred {
+            flush_deferred(&env, 2);
+        }
+        // Leaderboard payload: under the strictly-ascending bid rules an
+        // accepted bid always takes the lead, so `new_highest`/rank are
+        // constant today — front-ends consume the full schema regardless.
+        let new_highest = true;
+        let bidder_rank = 1u32;
+        env.events().publish(
+            (symbol_short!("bid"), id),
+            (bidder, effective, new_highest, bidder_rank),
+        );
+        Ok(())
+    }
+
+    /// Dry-run of every `place_bid` acceptance check — timing, whitelist,
+    /// KYC, tick, reserve, increment, TWAP floor — with no auth, transfers,
+    /// or state changes, so wallets can surface the exact rejection reason
+    /// before submitting.
+    pub fn validate_bid(
+        env: Env,
+        id: u64,
+        bidder: Address,
+        amount: i128,
+    ) -> Result<(), AuctionError> {
+        require_not_paused(&env)?;
+        let auction = read_auction(&env, id)?;
+        if settled(&env, id) {
+            return Err(AuctionError::AlreadySettled);
+        }
+        match auction.params.kind {
+            AuctionKind::English
+            | AuctionKind::Vickrey
+            | AuctionKind::SplitTopTwo
+            | AuctionKind::Reverse => {}
+            _ => return Err(AuctionError::WrongKind),
+        }
+        check_bid_window(&env, &auction, &bidder)?;
+        if auction.params.kind == AuctionKind::Reverse {
+            if amount < 0 || amount > reverse_maximum(&auction) {
+                return Err(AuctionError::BidTooLow);
+            }
+            return Ok(());
+        }
+        // Buy-now precedence, mirroring `place_bid`: an amount reaching the
+        // buy-now price is a purchase and never sees the tick or minimum
+        // checks, even off-tick or once the minimum has escalated past it.
+        if auction.params.kind == AuctionKind::English
+            && auction.params.buy_now_price > 0
+            && amount >= auction.params.buy_now_price
+        {
+            return Ok(());
+        }
+        if auction.params.bid_tick > 0 && amount % auction.params.bid_tick != 0 {
+            return Err(AuctionError::BidNotOnTick);
+        }
+        if auction.params.whole_units_only && amount % whole_unit(&env, &auction.params) != 0 {
+            return Err(AuctionError::BidNotOnTick);
+        }
+        if amount < bid_minimum(&env, &auction)? && !wins_tie(&env, id, &auction, &bidder, amount)
+        {
+            return Err(AuctionError::BidTooLow);
+        }
+        check_escrow_cap(&auction, amount)?;
+        Ok(())
+    }
+
+    /// Walk away from the lead before the auction ends. The retracting
+    /// leader gets their escrow back minus the configured penalty, which
+    /// goes to the seller. The runner-up is reinstated as leader when their
+    /// money is still inside the contract (their retained escrow for
+    /// `SplitTopTwo`, or an unclaimed pull-mode refund); otherwise the
+    /// auction simply reverts to having no leader.
+    pub fn retract_bid(env: Env, id: u64, bidder: Address) -> Result<(), AuctionError> {
+        require_not_paused(&env)?;
+        bidder.require_auth();
+        let mut auction = read_auction(&env, id)?;
+        if settled(&env, id) {
+            return Err(AuctionError::AlreadySettled);
+        }
+        match auction.params.kind {
+            AuctionKind::English | AuctionKind::Vickrey | AuctionKind::SplitTopTwo => {}
+            _ => return Err(AuctionError::WrongKind),
+        }
+        if env.ledger().timestamp() >= auction.end_time {
+            return Err(AuctionError::AuctionEnded);
+        }
+        if auction.highest_bidder.as_ref() != Some(&bidder) {
+            return Err(AuctionError::NotWinner);
+        }
+        let gross =
+            auction.highest_bid + bps_of(auction.highest_bid, auction.params.buyer_premium_bps);
+        let penalty = auction.params.retraction_penalty.min(gross);
+        settle_payout(&env, &auction.params.bid_token, &auction.params.seller, penalty);
+        settle_payout(&env, &auction.params.bid_token, &bidder, gross - penalty);
+
+        auction.highest_bidder = None;
+        auction.highest_bid = 0;
+        auction.refund_to = None;
+        if let Some(second) = auction.second_bidder.clone() {
+            let second_gross =
+                auction.second_bid + bps_of(auction.second_bid, auction.params.buyer_premium_bps);
+            let reinstate = match auction.params.kind {
+                // The runner-up's escrow never left for SplitTopTwo.
+                AuctionKind::SplitTopTwo => true,
+                _ => take_pending(&env, &auction.params.bid_token, &second, second_gross),
+            };
+            if reinstate {
+                auction.highest_bidder = Some(second);
+                auction.highest_bid = auction.second_bid;
+            }
+            auction.second_bidder = None;
+            auction.second_bid = 0;
+        }
+        write_auction(&env, id, &auction);
+        env.events()
+            .publish((symbol_short!("retracted"), id), (bidder, penalty));
+        Ok(())
+    }
+
+    /// Raise a bid by `additional` on top of value already inside the
+    /// contract: a leading bidder sends only the delta, and an outbid bidder
+    /// first recycles any pending bid-token refund into the new bid. The
+    /// resulting total goes through the normal `place_bid` checks and event.
+    pub fn increase_bid(
+        env: Env,
+        id: u64,
+        bidder: Address,
+        additional: i128,
+    ) -> Result<(), AuctionError> {
+        require_not_paused(&env)?;
+        bidder.require_auth();
+        let auction = read_auction(&env, id)?;
+        if additional <= 0 {
+            return Err(AuctionError::InvalidParams);
+        }
+        // Hand any pending bid-token refund back first so it can fund the
+        // new bid alongside the delta the bidder is sending.
+        let mut credit = 0i128;
+        let key = DataKey::PendingRefund(bidder.clone());
+        if let Some(mut balances) = env
+            .storage()
+            .persistent()
+            .get::<_, Map<Address, i128>>(&key)
+        {
+            credit = balances.get(auction.params.bid_token.clone()).unwrap_or(0);
+            if credit > 0 {
+                balances.remove(auction.params.bid_token.clone());
+                if balances.is_empty() {
+                    env.storage().persistent().remove(&key);
+                    let mut addrs: Vec<Address> = env
+                        .storage()
+                        .persistent()
+                        .get(&DataKey::PendingAddrs)
+                        .unwrap_or_else(|| Vec::new(&env));
+                    if let Some(pos) = addrs.first_index_of(&bidder) {
+                        addrs.remove(pos);
+                        env.storage().persistent().set(&DataKey::PendingAddrs, &addrs);
+                    }
+                } else {
+                    env.storage().persistent().set(&key, &balances);
+                }
+                escrow::release(&env, &auction.params.bid_token, &bidder, credit);
+            }
+        }
+        // A pending refund was grossed up with the buyer premium; only its
+        // net portion counts towards the bid amount.
+        let credit_net = credit * 10_000 / (10_000 + i128::from(auction.params.buyer_premium_bps));
+        let base = if auction.highest_bidder.as_ref() == Some(&bidder) {
+            auction.highest_bid
+        } else {
+            0
+        };
+        // `refund_to` on the auction belongs to the sitting leader; only
+        // carry it forward when that leader is the one raising. Anyone else
+        // inheriting it would route their own later refund to a stranger.
+        let refund_to = if auction.highest_bidder.as_ref() == Some(&bidder) {
+            auction.refund_to.clone()
+        } else {
+            None
+        };
+        Self::place_bid_internal(env, id, bidder, base + credit_net + additional, refund_to)
+    }
+
+    /// Voluntarily enlarge a good-faith deposit beyond the required stake,
+    /// e.g. to win `TieBreak::HighestDeposit` ties.
+    pub fn top_up_deposit(
+        env: Env,
+        id: u64,
+        bidder: Address,
+        amount: i128,
+    ) -> Result<(), AuctionError> {
+        require_not_paused(&env)?;
+        bidder.require_auth();
+        let auction = read_auction(&env, id)?;
+        if settled(&env, id) {
+            return Err(AuctionError::AlreadySettled);
+        }
+        if amount <= 0 {
+            return Err(AuctionError::InvalidParams);
+        }
+        escrow::lock(&env, &auction.params.bid_token, &bidder, amount);
+        let key = DataKey::Deposit(id, bidder.clone());
+        let held: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+        env.storage().persistent().set(&key, &(held + amount));
+        let mut depositors: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Depositors(id))
+            .unwrap_or_else(|| Vec::new(&env));
+        if !depositors.contains(&bidder) {
+            depositors.push_back(bidder.clone());
+            env.storage()
+                .persistent()
+                .set(&DataKey::Depositors(id), &depositors);
+        }
+        Ok(())
+    }
+
+    /// Halt bidding on a single auction at the seller's request (e.g. a
+    /// pending legal check). Time keeps running; only new bids are blocked.
+    pub fn seller_pause(env: Env, id: u64) -> Result<(), AuctionError> {
+        let mut auction = read_auction(&env, id)?;
+        if settled(&env, id) {
+            return Err(AuctionError::AlreadySettled);
+        }
+        auction.params.seller.require_auth();
+        if auction.seller_paused_at == 0 {
+            auction.seller_paused_at = env.ledger().timestamp();
+            write_auction(&env, id, &auction);
+        }
+        Ok(())
+    }
+
+    /// Re-open bidding after a `seller_pause`. With `freeze_clock_on_pause`
+    /// set, the end time moves out by exactly the paused interval.
+    pub fn seller_unpause(env: Env, id: u64) -> Result<(), AuctionError> {
+        let mut auction = read_auction(&env, id)?;
+        auction.params.seller.require_auth();
+        if auction.seller_paused_at > 0 {
+            if auction.params.freeze_clock_on_pause {
+                let paused_for = env.ledger().timestamp() - auction.seller_paused_at;
+                let end_time = compute_end_time(auction.end_time, paused_for)?;
+                apply_end_time(&mut auction, end_time)?;
+            }
+            auction.seller_paused_at = 0;
+            write_auction(&env, id, &auction);
+        }
+        Ok(())
+    }
+
+    /// Instant purchase. For English auctions this requires a configured
+    /// `buy_now_price`; for Dutch auctions the current decayed price applies.
+    /// The buyer sends `amount` which must cover the price; the auction
+    /// settles immediately.
+    pub fn buy_now(env: Env, id: u64, buyer: Address, amount: i128) -> Result<(), AuctionError> {
+        require_not_paused(&env)?;
+        buyer.require_auth();
+        Self::buy_now_internal(env, id, buyer, amount)
+    }
+
+    /// The buy-now settlement itself, shared with the `place_bid` path that
+    /// promotes a buy-now-priced bid into a purchase. Callers have already
+    /// authenticated `buyer` and checked the pause switch.
+    fn buy_now_internal(env: Env, id: u64, buyer: Address, amount: i128) -> Result<(), AuctionError> {
+        let mut auction = read_auction(&env, id)?;
+        if settled(&env, id) {
+            return Err(AuctionError::AlreadySettled);
+        }
+        check_bid_window(&env, &auction, &buyer)?;
+        let now = env.ledger().timestamp();
+        let price = match auction.params.kind {
+            AuctionKind::English => {
+                if auction.params.buy_now_price == 0 {
+                    return Err(AuctionError::NoBuyNow);
+                }
+                auction.params.buy_now_price
+            }
+            AuctionKind::Dutch => dutch_price(&auction, now),
+            _ => return Err(AuctionError::WrongKind),
+        };
+        if amount < price {
+            return Err(AuctionError::BidTooLow);
+        }
+        recall_asset(&env, &auction, auction.params.asset_amount);
+
+        // Only the price (plus any buyer premium, same as a winning bid
+        // pays) is charged; anything offered above it stays with the buyer.
+        let premium = bps_of(price, auction.params.buyer_premium_bps);
+        escrow::lock(&env, &auction.params.bid_token, &buyer, price + premium);
+        if let Some(prev) = auction.highest_bidder.clone() {
+            let dest = auction.refund_to.clone().unwrap_or(prev);
+            refund_bidder(&env, id, &auction, &dest, auction.highest_bid);
+        }
+        auction.highest_bidder = Some(buyer.clone());
+        auction.highest_bid = price;
+        auction.last_bid_time = now;
+        // The buyer is a participant for listing-fee purposes even if they
+        // never placed a conventional bid.
+        record_bidder(&env, id, &mut auction, &buyer);
+
+        mark_settled(&env, id);
+        auction.settled_at = now;
+        settle_listing_fee(&env, id, &auction);
+        deliver_asset(&env, &auction, &buyer, auction.params.asset_amount);
+        move_bundle(&env, &auction, &buyer);
+        auction.remaining = 0;
+        distribute(&env, id, &auction, price)?;
+        pay_premium(&env, &auction, price)?;
+        release_deposits(&env, id, &auction, Some(&buyer));
+        record_outcome(&env, id, &auction, Some(buyer.clone()), price);
+        write_auction(&env, id, &auction);
+        env.events()
+            .publish((symbol_short!("sale"), id), (buyer.clone(), price));
+        env.events()
+            .publish((symbol_short!("settled"), id), (Some(buyer), price));
+        Ok(())
+    }
+
+    /// Buy part of a divisible lot at the fixed per-unit price. Units are
+    /// delivered immediately; proceeds accumulate until `settle`.
+    pub fn buy_units(
+        env: Env,
+        id: u64,
+        buyer: Address,
+        units: i128,
+    ) -> Result<(), AuctionError> {
+        require_not_paused(&env)?;
+        buyer.require_auth();
+        let mut auction = read_auction(&env, id)?;
+        if settled(&env, id) {
+            return Err(AuctionError::AlreadySettled);
+        }
+        if auction.params.kind != AuctionKind::Divisible {
+            return Err(AuctionError::WrongKind);
+        }
+        check_bid_window(&env, &auction, &buyer)?;
+        if units <= 0 {
+            return Err(AuctionError::InvalidParams);
+        }
+        if units > auction.remaining {
+            return Err(AuctionError::InsufficientRemaining);
+        }
+        let cost = units
+            .checked_mul(auction.params.reserve_price)
+            .ok_or(AuctionError::InvalidParams)?;
+        recall_asset(&env, &auction, units);
+        escrow::lock(&env, &auction.params.bid_token, &buyer, cost);
+        escrow::release(&env, &auction.params.asset, &buyer, units);
+        auction.remaining -= units;
+        auction.proceeds += cost;
+        auction.last_bid_time = env.ledger().timestamp();
+        write_auction(&env, id, &auction);
+        env.events()
+            .publish((symbol_short!("fill"), id), (buyer, units));
+        Ok(())
+    }
+
+    /// Free cancellation of a scheduled auction before bidding opens. The
+    /// asset (and bundle) go straight back and the auction is closed out.
+    pub fn cancel_before_start(env: Env, id: u64) -> Result<(), AuctionError> {
+        let mut auction = read_auction(&env, id)?;
+        auction.params.seller.require_auth();
+        if settled(&env, id) {
+            return Err(AuctionError::AlreadySettled);
+        }
+        if env.ledger().timestamp() >= auction.start_time {
+            return Err(AuctionError::AlreadyStarted);
+        }
+        recall_asset(&env, &auction, auction.params.asset_amount);
+        mark_settled(&env, id);
+        auction.settled_at = env.ledger().timestamp();
+        // The anti-spam rationale applies to cancellations too: no bidders
+        // showed up, so the listing fee stays with the collector.
+        settle_listing_fee(&env, id, &auction);
+        if !auction.params.mint_on_settle {
+            escrow::release(
+                &env,
+                &auction.params.asset,
+                &auction.params.seller,
+                auction.params.asset_amount,
+            );
+            move_bundle(&env, &auction, &auction.params.seller);
+        }
+        if auction.params.kind == AuctionKind::Reverse {
+            // The payment budget locked at creation goes home with the lot.
+            escrow::release(
+                &env,
+                &auction.params.bid_token,
+                &auction.params.seller,
+                auction.params.reserve_price,
+            );
+        }
+        auction.remaining = 0;
+        record_outcome(&env, id, &auction, None, 0);
+        write_auction(&env, id, &auction);
+        env.storage().persistent().set(
+            &DataKey::LastCancel(auction.params.seller.clone()),
+            &env.ledger().timestamp(),
+        );
+        env.events()
+            .publish((symbol_short!("cancelled"), id), auction.params.seller);
+        Ok(())
+    }
+
+    /// Convert a bid-less English auction into a fixed-price listing: sets
+    /// the buy-now price and lifts the reserve to match, so the only way to
+    /// win becomes paying that price.
+    pub fn convert_to_fixed_price(env: Env, id: u64, price: i128) -> Result<(), AuctionError> {
+        let mut auction = read_auction(&env, id)?;
+        auction.params.seller.require_auth();
+        if settled(&env, id) {
+            return Err(AuctionError::AlreadySettled);
+        }
+        if auction.params.kind != AuctionKind::English {
+            return Err(AuctionError::WrongKind);
+        }
+        if auction.highest_bidder.is_some() {
+            return Err(AuctionError::AlreadyStarted);
+        }
+        if price <= 0 {
+            return Err(AuctionError::InvalidParams);
+        }
+        auction.params.buy_now_price = price;
+        auction.params.reserve_price = price;
+        auction.params.reserve_ratio = 0;
+        write_auction(&env, id, &auction);
+        env.events()
+            .publish((symbol_short!("fixed"), id), price);
+        Ok(())
+    }
+
+    /// Seller-initiated extension of the bidding window.
+    pub fn extend_auction(env: Env, id: u64, additional: u64) -> Result<(), AuctionError> {
+        let mut auction = read_auction(&env, id)?;
+        auction.params.seller.require_auth();
+        if settled(&env, id) {
+            return Err(AuctionError::AlreadySettled);
+        }
+        if additional == 0 {
+            return Err(AuctionError::InvalidParams);
+        }
+        let end_time = compute_end_time(auction.end_time, additional)?;
+        // Extending an auction that already lapsed must re-open a real
+        // window, not produce an end still in the past.
+        if end_time <= env.ledger().timestamp() {
+            return Err(AuctionError::EndInPast);
+        }
+        apply_end_time(&mut auction, end_time)?;
+        write_auction(&env, id, &auction);
+        env.events()
+            .publish((symbol_short!("extended"), id), auction.end_time);
+        Ok(())
+    }
+
+    /// Close out an ended auction: pay the seller (minus fee and royalty),
+    /// deliver the asset or hand it back, and release deposits.
+    pub fn settle(env: Env, id: u64) -> Result<(), AuctionError> {
+        let mut auction = read_auction(&env, id)?;
+        if settled(&env, id) {
+            return Err(AuctionError::AlreadySettled);
+        }
+        if auction.params.kind == AuctionKind::Dutch {
+            // A sold Dutch auction settles inside `buy_now`.
+            return Err(AuctionError::WrongKind);
+        }
+        let eligible_at = compute_end_time(auction.end_time, auction.params.timing.settle_delay)?;
+        if env.ledger().timestamp() < eligible_at {
+            return Err(AuctionError::AuctionNotEnded);
+        }
+        if auction.params.timing.quiet_period > 0 && auction.highest_bidder.is_some() {
+            let quiet_until =
+                compute_end_time(auction.last_bid_time, auction.params.timing.quiet_period)?;
+            if env.ledger().timestamp() < quiet_until {
+                return Err(AuctionError::AuctionNotEnded);
+            }
+        }
+        recall_asset(&env, &auction, auction.remaining);
+        // Defensive: if the escrowed asset has somehow left the contract
+        // (token clawback, upgrade bug), delivering it would trap mid-settle.
+        // Close the auction out as a refund instead so the winner's escrow is
+        // never stranded behind an unsettleable auction.
+        if auction.remaining > 0 && !auction.params.mint_on_settle {
+            let held =
+                token::Client::new(&env, &auction.params.asset).balance(&env.current_contract_address());
+            if held < auction.remaining {
+                mark_settled(&env, id);
+                auction.settled_at = env.ledger().timestamp();
+                settle_listing_fee(&env, id, &auction);
+                if auction.params.kind == AuctionKind::Reverse {
+                    // Reverse takers never escrowed anything; the stranded
+                    // funds here are the seller's payment budget.
+                    settle_payout(
+                        &env,
+                        &auction.params.bid_token,
+                        &auction.params.seller,
+                        auction.params.reserve_price,
+                    );
+                } else {
+                    if let Some(w) = auction.highest_bidder.clone() {
+                        let dest = auction.refund_to.clone().unwrap_or_else(|| w.clone());
+                        refund_bidder(&env, id, &auction, &dest, auction.highest_bid);
+                    }
+                    if auction.params.kind == AuctionKind::SplitTopTwo {
+                        if let Some(runner_up) = auction.second_bidder.clone() {
+                            refund_bidder(&env, id, &auction, &runner_up, auction.second_bid);
+                        }
+                    }
+                }
+                // Units already sold through `buy_units` are done deals:
+                // their proceeds go through the normal split rather than
+                // staying stranded alongside the undeliverable remainder.
+                let mut final_price = 0i128;
+                if auction.params.kind == AuctionKind::Divisible && auction.proceeds > 0 {
+                    let held_bid = token::Client::new(&env, &auction.params.bid_token)
+                        .balance(&env.current_contract_address())
+                        .max(0);
+                    final_price = auction.proceeds.min(held_bid);
+                    distribute(&env, id, &auction, final_price)?;
+                }
+                release_deposits(&env, id, &auction, None);
+                auction.remaining = 0;
+                record_outcome(&env, id, &auction, None, final_price);
+                write_auction(&env, id, &auction);
+                env.events().publish(
+                    (symbol_short!("assetgone"), id),
+                    AuctionError::AssetMissing as u32,
+                );
+                return Ok(());
+            }
+        }
+
+        // A no-sale with relists left re-opens instead of settling: the asset
+        // is already escrowed, so only the window moves.
+        if auction.highest_bidder.is_none()
+            && auction.params.relist.auto
+            && auction.relists_done < auction.params.relist.count
+        {
+            let now = env.ledger().timestamp();
+            auction.start_time = now;
+            let end_time = compute_end_time(now, auction.params.duration)?;
+            apply_end_time(&mut auction, end_time)?;
+            auction.relists_done += 1;
+            write_auction(&env, id, &auction);
+            env.events().publish(
+                (symbol_short!("relisted"), id),
+                (auction.relists_done, auction.end_time),
+            );
+            return Ok(());
+        }
+        mark_settled(&env, id);
+        auction.settled_at = env.ledger().timestamp();
+        settle_listing_fee(&env, id, &auction);
+
+        let winner = auction.highest_bidder.clone();
+        // A soft reserve turns an under-target sale into a no-sale at
+        // settlement without ever blocking bids.
+        let soft_reserve_missed = auction.params.soft_reserve > 0
+            && winner.is_some()
+            && auction.highest_bid < auction.params.soft_reserve;
+        if soft_reserve_missed
+            && matches!(
+                auction.params.kind,
+                AuctionKind::English | AuctionKind::Vickrey
+            )
+        {
+            let w = winner.clone().unwrap();
+            let dest = auction.refund_to.clone().unwrap_or_else(|| w.clone());
+            refund_bidder(&env, id, &auction, &dest, auction.highest_bid);
+            if !auction.params.mint_on_settle {
+                settle_payout(
+                    &env,
+                    &auction.params.asset,
+                    &auction.params.seller,
+                    auction.params.asset_amount,
+                );
+                move_bundle(&env, &auction, &auction.params.seller);
+            }
+            auction.remaining = 0;
+            release_deposits(&env, id, &auction, None);
+            record_outcome(&env, id, &auction, None, 0);
+            write_auction(&env, id, &auction);
+            env.events()
+                .publish((symbol_short!("settled"), id), (None::<Address>, 0i128));
+            return Ok(());
+        }
+        // Reconcile escrow against the payout about to happen: a misbehaving
+        // bid token (clawback, skim on transfer) can leave the contract
+        // holding less than the final price. Policy: cap the payout at the
+        // contract's pooled bid-token balance — every split scales down
+        // together through the reduced amount — so settlement never traps
+        // mid-transfer. The pool is shared across auctions in the same token
+        // and nothing attributes a clawback to the auction it actually hit,
+        // so a shortfall is borne by whoever settles after the pool runs
+        // down, not necessarily the auction whose funds were taken.
+        // Per-auction attribution would need the full-ledger walk
+        // `get_escrow_breakdown` does, which is too heavy for this path.
+        let held_bid = token::Client::new(&env, &auction.params.bid_token)
+            .balance(&env.current_contract_address())
+            .max(0);
+        // What actually changed hands, for the outcome record and event:
+        // the standing highest bid is wrong for Vickrey (second price),
+        // SplitTopTwo (combined price) and shortfall-scaled payouts.
+        let mut final_price: i128 = 0;
+        match auction.params.kind {
+            AuctionKind::English => match &winner {
+                Some(w) if auction.params.installments.deadline > 0 => {
+                    // Installment sale: keep only the down payment, hand the
+                    // rest of the escrow back, and leave the asset locked
+                    // until `pay_installment` covers the hammer price.
+                    let price = auction.highest_bid.min(held_bid);
+                    let down = bps_of(price, auction.params.installments.down_bps);
+                    let dest = auction.refund_to.clone().unwrap_or_else(|| w.clone());
+                    refund_bidder(&env, id, &auction, &dest, auction.highest_bid - down);
+                    env.storage().persistent().set(&DataKey::Installment(id), &down);
+                    release_deposits(&env, id, &auction, Some(w));
+                    record_outcome(&env, id, &auction, winner.clone(), price);
+                    write_auction(&env, id, &auction);
+                    env.events()
+                        .publish((symbol_short!("instplan"), id), (w.clone(), down));
+                    return Ok(());
+                }
+                Some(w) => {
+                    deliver_asset(&env, &auction, w, auction.params.asset_amount);
+                    move_bundle(&env, &auction, w);
+                    auction.remaining = 0;
+                    let price = auction.highest_bid.min(held_bid);
+                    distribute(&env, id, &auction, price)?;
+                    pay_premium(&env, &auction, price)?;
+                    final_price = price;
+                }
+                None => {
+                    // A no-sale mints nothing; escrowed lots go home.
+                    if !auction.params.mint_on_settle {
+                        settle_payout(
+                            &env,
+                            &auction.params.asset,
+                            &auction.params.seller,
+                            auction.params.asset_amount,
+                        );
+                        move_bundle(&env, &auction, &auction.params.seller);
+                    }
+                    auction.remaining = 0;
+                }
+            },
+            AuctionKind::Vickrey => match &winner {
+                Some(w) => {
+                    // Winner pays the second price (never below reserve);
+                    // the overbid comes back, the asset is claimed later.
+                    let price = auction
+                        .second_bid
+                        .max(effective_reserve(&auction.params))
+                        .min(held_bid);
+                    let dest = auction.refund_to.clone().unwrap_or_else(|| w.clone());
+                    refund_bidder(&env, id, &auction, &dest, auction.highest_bid - price);
+                    distribute(&env, id, &auction, price)?;
+                    pay_premium(&env, &auction, price)?;
+                    final_price = price;
+                }
+                None => {
+                    settle_payout(
+                        &env,
+                        &auction.params.asset,
+                        &auction.params.seller,
+                        auction.params.asset_amount,
+                    );
+                    move_bundle(&env, &auction, &auction.params.seller);
+                    auction.remaining = 0;
+                }
+            },
+            AuctionKind::SplitTopTwo => match &winner {
+                Some(w) => {
+                    match &auction.second_bidder {
+                        Some(runner_up) => {
+                            let winner_share = auction.params.asset_amount * 70 / 100;
+                            settle_payout(&env, &auction.params.asset, w, winner_share);
+                            settle_payout(
+                                &env,
+                                &auction.params.asset,
+                                runner_up,
+                                auction.params.asset_amount - winner_share,
+                            );
+                            // Both paying bids escrowed a premium, so it is
+                            // owed on their combined price.
+                            let price =
+                                (auction.highest_bid + auction.second_bid).min(held_bid);
+                            distribute(&env, id, &auction, price)?;
+                            pay_premium(&env, &auction, price)?;
+                            final_price = price;
+                        }
+                        // A single bidder takes the whole lot at their bid.
+                        None => {
+                            settle_payout(&env, &auction.params.asset, w, auction.params.asset_amount);
+                            let price = auction.highest_bid.min(held_bid);
+                            distribute(&env, id, &auction, price)?;
+                            pay_premium(&env, &auction, price)?;
+                            final_price = price;
+                        }
+                    }
+                    move_bundle(&env, &auction, w);
+                    auction.remaining = 0;
+                }
+                None => {
+                    settle_payout(
+                        &env,
+                        &auction.params.asset,
+                        &auction.params.seller,
+                        auction.params.asset_amount,
+                    );
+                    move_bundle(&env, &auction, &auction.params.seller);
+                    auction.remaining = 0;
+                }
+            },
+            AuctionKind::Reverse => match &winner {
+                Some(w) => {
+                    settle_payout(&env, &auction.params.asset, w, auction.params.asset_amount);
+                    move_bundle(&env, &auction, w);
+                    auction.remaining = 0;
+                    // The taker's payment comes out of the escrowed budget;
+                    // the unspent remainder goes home. No protocol fee — no
+                    // proceeds changed hands in the seller's favor.
+                    settle_payout(&env, &auction.params.bid_token, w, auction.highest_bid);
+                    settle_payout(
+                        &env,
+                        &auction.params.bid_token,
+                        &auction.params.seller,
+                        auction.params.reserve_price - auction.highest_bid,
+                    );
+                    final_price = auction.highest_bid;
+                }
+                None => {
+                    settle_payout(
+                        &env,
+                        &auction.params.asset,
+                        &auction.params.seller,
+                        auction.params.asset_amount,
+                    );
+                    settle_payout(
+                        &env,
+                        &auction.params.bid_token,
+                        &auction.params.seller,
+                        auction.params.reserve_price,
+                    );
+                    move_bundle(&env, &auction, &auction.params.seller);
+                    auction.remaining = 0;
+                }
+            },
+            AuctionKind::Divisible => {
+                final_price = auction.proceeds.min(held_bid);
+                distribute(&env, id, &auction, final_price)?;
+                if auction.remaining > 0 {
+                    settle_payout(
+                        &env,
+                        &auction.params.asset,
+                        &auction.params.seller,
+                        auction.remaining,
+                    );
+                    auction.remaining = 0;
+                }
+            }
+            AuctionKind::Dutch => unreachable!(),
+        }
+        release_deposits(&env, id, &auction, winner.as_ref());
+        record_outcome(&env, id, &auction, winner.clone(), final_price);
+        write_auction(&env, id, &auction);
+        env.events()
+            .publish((symbol_short!("settled"), id), (winner, final_price));
+        Ok(())
+    }
+
+    /// Vickrey winner pulls the asset after settlement.
+    pub fn claim(env: Env, id: u64) -> Result<(), AuctionError> {
+        let mut auction = read_auction(&env, id)?;
+        if auction.params.kind != AuctionKind::Vickrey {
+            return Err(AuctionError::WrongKind);
+        }
+        if !settled(&env, id) {
+            return Err(AuctionError::AuctionNotEnded);
+        }
+        let winner = auction
+            .highest_bidder
+            .clone()
+            .ok_or(AuctionError::NotWinner)?;
+        winner.require_auth();
+        if auction.remaining <= 0 {
+            return Err(AuctionError::NothingToWithdraw);
+        }
+        let held = token::Client::new(&env, &auction.params.asset)
+            .balance(&env.current_contract_address());
+        if held < auction.params.asset_amount {
+            return Err(AuctionError::AssetMissing);
+        }
+        escrow::release(
+            &env,
+            &auction.params.asset,
+            &winner,
+            auction.params.asset_amount,
+        );
+        move_bundle(&env, &auction, &winner);
+        auction.remaining = 0;
+        write_auction(&env, id, &auction);
+        Ok(())
+    }
+
+    /// After the claim deadline passes with the asset still unclaimed, the
+    /// seller takes it back. The winner's payment stays with the seller; a
+    /// winner who wants it must claim in time.
+    pub fn reclaim_unclaimed(env: Env, id: u64) -> Result<(), AuctionError> {
+        let mut auction = read_auction(&env, id)?;
+        auction.params.seller.require_auth();
+        if auction.params.kind != AuctionKind::Vickrey {
+            return Err(AuctionError::WrongKind);
+        }
+        if !settled(&env, id) {
+            return Err(AuctionError::AuctionNotEnded);
+        }
+        if auction.remaining <= 0 {
+            return Err(AuctionError::NothingToWithdraw);
+        }
+        if auction.params.timing.claim_deadline == 0 {
+            return Err(AuctionError::InvalidParams);
+        }
+        let deadline = compute_end_time(auction.settled_at, auction.params.timing.claim_deadline)?;
+        if env.ledger().timestamp() < deadline {
+            return Err(AuctionError::AuctionNotEnded);
+        }
+        escrow::release(
+            &env,
+            &auction.params.asset,
+            &auction.params.seller,
+            auction.params.asset_amount,
+        );
+        move_bundle(&env, &auction, &auction.params.seller);
+        auction.remaining = 0;
+        write_auction(&env, id, &auction);
+        env.events()
+            .publish((symbol_short!("reclaimed"), id), auction.params.seller);
+        Ok(())
+    }
+
+    /// Close out a Dutch auction that reached its floor with no taker: once
+    /// `end_time` passes unsold, the asset (and any bundle) goes home to the
+    /// seller. The Dutch counterpart of the English no-sale path in
+    /// `settle`, which otherwise rejects Dutch auctions outright.
+    pub fn close_unsold(env: Env, id: u64) -> Result<(), AuctionError> {
+        let mut auction = read_auction(&env, id)?;
+        if auction.params.kind != AuctionKind::Dutch {
+            return Err(AuctionError::WrongKind);
+        }
+        // A sold Dutch auction settled inside `buy_now`.
+        if settled(&env, id) {
+            return Err(AuctionError::AlreadySettled);
+        }
+        if env.ledger().timestamp() < auction.end_time {
+            return Err(AuctionError::AuctionNotEnded);
+        }
+        recall_asset(&env, &auction, auction.params.asset_amount);
+        mark_settled(&env, id);
+        auction.settled_at = env.ledger().timestamp();
+        settle_listing_fee(&env, id, &auction);
+        settle_payout(
+            &env,
+            &auction.params.asset,
+            &auction.params.seller,
+            auction.params.asset_amount,
+        );
+        move_bundle(&env, &auction, &auction.params.seller);
+        auction.remaining = 0;
+        release_deposits(&env, id, &auction, None);
+        record_outcome(&env, id, &auction, None, 0);
+        write_auction(&env, id, &auction);
+        env.events()
+            .publish((symbol_short!("unsold"), id), auction.params.seller);
+        Ok(())
+    }
+
+    /// Pay the next installment on a settled installment sale. Only the
+    /// recorded winner may pay, overpaying past the hammer price is
+    /// rejected, and covering the full price delivers the asset and
+    /// distributes the proceeds on the spot.
+    pub fn pay_installment(env: Env, id: u64, amount: i128) -> Result<(), AuctionError> {
+        require_not_paused(&env)?;
+        let mut auction = read_auction(&env, id)?;
+        let key = DataKey::Installment(id);
+        let paid: i128 = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(AuctionError::WrongKind)?;
+        let outcome: Outcome = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Outcome(id))
+            .ok_or(AuctionError::AuctionNotFound)?;
+        let winner = outcome.winner.ok_or(AuctionError::NotWinner)?;
+        winner.require_auth();
+        let deadline =
+            compute_end_time(auction.settled_at, auction.params.installments.deadline)?;
+        if env.ledger().timestamp() >= deadline {
+            return Err(AuctionError::AuctionEnded);
+        }
+        if amount <= 0 || amount > outcome.final_price - paid {
+            return Err(AuctionError::InvalidParams);
+        }
+        escrow::lock(&env, &auction.params.bid_token, &winner, amount);
+        let paid = paid + amount;
+        if paid >= outcome.final_price {
+            env.storage().persistent().remove(&key);
+            deliver_asset(&env, &auction, &winner, auction.params.asset_amount);
+            move_bundle(&env, &auction, &winner);
+            auction.remaining = 0;
+            distribute(&env, id, &auction, outcome.final_price)?;
+            write_auction(&env, id, &auction);
+            env.events()
+                .publish((symbol_short!("paidfull"), id), winner);
+        } else {
+            env.storage().persistent().set(&key, &paid);
+            env.events()
+                .publish((symbol_short!("instpaid"), id), (winner, paid));
+        }
+        Ok(())
+    }
+
+    /// Close out a defaulted installment sale once its deadline has lapsed
+    /// unpaid (permissionless, like `cleanup`): the asset goes back to the
+    /// seller, the winner recovers `refund_bps` of what they paid in, and
+    /// the seller keeps the rest as compensation.
+    pub fn default_installment(env: Env, id: u64) -> Result<(), AuctionError> {
+        let mut auction = read_auction(&env, id)?;
+        let key = DataKey::Installment(id);
+        let paid: i128 = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(AuctionError::WrongKind)?;
+        let deadline =
+            compute_end_time(auction.settled_at, auction.params.installments.deadline)?;
+        if env.ledger().timestamp() < deadline {
+            return Err(AuctionError::AuctionNotEnded);
+        }
+        env.storage().persistent().remove(&key);
+        let outcome: Outcome = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Outcome(id))
+            .ok_or(AuctionError::AuctionNotFound)?;
+        let winner = outcome.winner.ok_or(AuctionError::NotWinner)?;
+        let returned = bps_of(paid, auction.params.installments.refund_bps);
+        settle_payout(&env, &auction.params.bid_token, &winner, returned);
+        settle_payout(
+            &env,
+            &auction.params.bid_token,
+            &auction.params.seller,
+            paid - returned,
+        );
+        settle_payout(
+            &env,
+            &auction.params.asset,
+            &auction.params.seller,
+            auction.params.asset_amount,
+        );
+        move_bundle(&env, &auction, &auction.params.seller);
+        auction.remaining = 0;
+        write_auction(&env, id, &auction);
+        env.events()
+            .publish((symbol_short!("defaulted"), id), (winner, paid - returned));
+        Ok(())
+    }
+
+    /// Permissionless housekeeping: drop the per-id storage of a settled and
+    /// fully drained auction, leaving only its compact `Outcome` behind.
+    pub fn cleanup(env: Env, id: u64) -> Result<(), AuctionError> {
+        let auction = read_auction(&env, id)?;
+        if !settled(&env, id) {
+            return Err(AuctionError::AuctionNotEnded);
+        }
+        // A Vickrey winner's unclaimed asset (or a pending seller reclaim)
+        // still lives under these keys.
+        if auction.remaining > 0 {
+            return Err(AuctionError::ClaimsOutstanding);
+        }
+        env.storage().persistent().remove(&DataKey::Auction(id));
+        env.storage().persistent().remove(&DataKey::IsSettle(id));
+        env.storage().persistent().remove(&DataKey::Depositors(id));
+        env.storage().persistent().remove(&DataKey::Bidders(id));
+        env.storage().persistent().remove(&DataKey::ForfeitedDeposit(id));
+        Ok(())
+    }
+
+    /// Pull-mode refunds: send everything pending for `who` back to them.
+    pub fn withdraw(env: Env, who: Address) -> Result<(), AuctionError> {
+        who.require_auth();
+        let key = DataKey::PendingRefund(who.clone());
+        let balances: Map<Address, i128> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(AuctionError::NothingToWithdraw)?;
+        env.storage().persistent().remove(&key);
+        let mut addrs: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::PendingAddrs)
+            .unwrap_or_else(|| Vec::new(&env));
+        if let Some(pos) = addrs.first_index_of(&who) {
+            addrs.remove(pos);
+            env.storage().persistent().set(&DataKey::PendingAddrs, &addrs);
+        }
+        for (token_addr, amount) in balances.iter() {
+            escrow::release(&env, &token_addr, &who, amount);
+        }
+        mark_refunded(&env, &who);
+        Ok(())
+    }
+
+    /// Push out pending refunds (e.g. after a big auction settles), at most
+    /// `max_per_call` addresses per invocation so one call can't blow the
+    /// resource budget. Returns whether addresses are still waiting, so
+    /// callers keep invoking until it comes back `false`.
+    pub fn refund_all(env: Env, max_per_call: u32) -> Result<bool, AuctionError> {
+        if max_per_call == 0 {
+            return Err(AuctionError::InvalidParams);
+        }
+        let snapshot: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::PendingAddrs)
+            .unwrap_or_else(|| Vec::new(&env));
+        let mut processed: u32 = 0;
+        for who in snapshot.iter() {
+            if processed >= max_per_call {
+                break;
+            }
+            let key = DataKey::PendingRefund(who.clone());
+            let balances: Map<Address, i128> = match env.storage().persistent().get(&key) {
+                Some(balances) => balances,
+                None => continue,
+            };
+            // Same discipline as `flush_deferred`: clear the entry and its
+            // address-list slot first, then re-park whatever a recipient
+            // rejects. An address that cannot receive (frozen trustline,
+            // blocklisted) moves to the back of the queue instead of
+            // trapping the whole call and everyone behind it.
+            env.storage().persistent().remove(&key);
+            let mut list: Vec<Address> = env
+                .storage()
+                .persistent()
+                .get(&DataKey::PendingAddrs)
+                .unwrap_or_else(|| Vec::new(&env));
+            if let Some(pos) = list.first_index_of(&who) {
+                list.remove(pos);
+                env.storage().persistent().set(&DataKey::PendingAddrs, &list);
+            }
+            let mut delivered = false;
+            for (token_addr, amount) in balances.iter() {
+                if escrow::try_release(&env, &token_addr, &who, amount).is_ok() {
+                    delivered = true;
+                } else {
+                    add_pending(&env, &token_addr, &who, amount);
+                }
+            }
+            if delivered {
+                mark_refunded(&env, &who);
+            }
+            processed += 1;
+        }
+        let remaining: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::PendingAddrs)
+            .unwrap_or_else(|| Vec::new(&env));
+        if remaining.is_empty() {
+            env.storage().persistent().remove(&DataKey::PendingAddrs);
+        }
+        Ok(!remaining.is_empty())
+    }
+
+    /// Full stored state of an auction.
+    pub fn get_auction(env: Env, id: u64) -> Result<Auction, AuctionError> {
+        read_auction(&env, id)
+    }
+
+    /// Semantic version of the deployed contract code.
+    pub fn version(_env: Env) -> (u32, u32, u32) {
+        (0, 1, 0)
+    }
+
+    /// The auction formats this build can run, as `AuctionKind` names in
+    /// declaration order — a stable subset of `features` for front-ends that
+    /// only care about formats.
+    pub fn supported_kinds(env: Env) -> Vec<Symbol> {
+        soroban_sdk::vec![
+            &env,
+            symbol_short!("english"),
+            symbol_short!("dutch"),
+            symbol_short!("vickrey"),
+            symbol_short!("divisible"),
+            symbol_short!("split_two"),
+            symbol_short!("reverse"),
+        ]
+    }
+
+    /// Capabilities of this instance: the formats and mechanics compiled in,
+    /// plus any admin-configured gates (currently `kyc`) that are active.
+    pub fn features(env: Env) -> Vec<Symbol> {
+        let mut list = soroban_sdk::vec![
+            &env,
+            symbol_short!("english"),
+            symbol_short!("dutch"),
+            symbol_short!("vickrey"),
+            symbol_short!("divisible"),
+            symbol_short!("split_two"),
+            symbol_short!("antisnipe"),
+            symbol_short!("buy_now"),
+            symbol_short!("pull_ref"),
+            symbol_short!("premium"),
+            symbol_short!("burn"),
+            symbol_short!("relist"),
+        ];
+        if env.storage().instance().has(&DataKey::KycContract) {
+            list.push_back(symbol_short!("kyc"));
+        }
+        list
+    }
+
+    /// Everything the winner actually paid for a settled auction: hammer
+    /// price, buyer premium, and the forfeited good-faith deposit — as
+    /// opposed to what the seller received after fees.
+    pub fn get_winner_total_cost(env: Env, id: u64) -> Result<i128, AuctionError> {
+        let auction = read_auction(&env, id)?;
+        if !settled(&env, id) {
+            return Err(AuctionError::AuctionNotEnded);
+        }
+        let outcome: Outcome = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Outcome(id))
+            .ok_or(AuctionError::AuctionNotFound)?;
+        if outcome.winner.is_none() {
+            return Err(AuctionError::NotWinner);
+        }
+        let forfeited: i128 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::ForfeitedDeposit(id))
+            .unwrap_or(0);
+        Ok(outcome.final_price
+            + bps_of(outcome.final_price, auction.params.buyer_premium_bps)
+            + forfeited)
+    }
+
+    /// Whether `who` has ever won an auction on this contract, for
+    /// reputation-style integrations.
+    pub fn has_won(env: Env, who: Address) -> bool {
+        env.storage()
+            .persistent()
+            .get(&DataKey::HasWon(who))
+            .unwrap_or(false)
+    }
+
+    /// Whether `who` has ever had refund money delivered back to them,
+    /// whether pushed on outbid or pulled via `withdraw`.
+    pub fn was_refunded(env: Env, who: Address) -> bool {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Refunded(who))
+            .unwrap_or(false)
+    }
+
+    /// Whether `withdraw` would deliver anything for `who` — lets a UI skip
+    /// submitting a no-op transaction.
+    pub fn has_pending(env: Env, who: Address) -> bool {
+        Self::get_pending_refund(env, who) > 0
+    }
+
+    /// Total refunds waiting for `who` to withdraw, summed across bid tokens.
+    pub fn get_pending_refund(env: Env, who: Address) -> i128 {
+        let balances: Map<Address, i128> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::PendingRefund(who))
+            .unwrap_or_else(|| Map::new(&env));
+        let mut total = 0i128;
+        for (_, amount) in balances.iter() {
+            total += amount;
+        }
+        total
+    }
+
+    /// Fresh funds `who` must bring to place the minimum next bid on an
+    /// auction, after netting off whatever of the auction's bid token is
+    /// already parked for them as a pending refund (see `increase_bid`,
+    /// which recycles that credit). Floored at zero.
+    pub fn net_required_for(env: Env, id: u64, who: Address) -> Result<i128, AuctionError> {
+        let auction = read_auction(&env, id)?;
+        let minimum = bid_minimum(&env, &auction)?;
+        let balances: Map<Address, i128> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::PendingRefund(who))
+            .unwrap_or_else(|| Map::new(&env));
+        let parked = balances.get(auction.params.bid_token.clone()).unwrap_or(0);
+        Ok((minimum - parked).max(0))
+    }
+
+    /// Convert `amount` of the bid token into a reference currency using a
+    /// price oracle exposing `price() -> (i128, u32)` (price per whole token
+    /// and its decimal scale). Read-only display helper; auction logic never
+    /// depends on it.
+    pub fn quote_in(env: Env, oracle: Address, amount: i128) -> Result<i128, AuctionError> {
+        let (price, decimals): (i128, u32) =
+            env.invoke_contract(&oracle, &symbol_short!("price"), Vec::new(&env));
+        let scale = 10i128
+            .checked_pow(decimals)
+            .ok_or(AuctionError::MathOverflow)?;
+        amount
+            .checked_mul(price)
+            .ok_or(AuctionError::MathOverflow)
+            .map(|value| value / scale)
+    }
+
+    /// Total number of auctions ever created, straight off the counter.
+    pub fn get_auction_count(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&DataKey::Counter)
+            .unwrap_or(0)
+    }
+
+    /// The dashboard aggregates in one read, straight off the maintained
+    /// counters — no per-auction iteration.
+    pub fn get_stats(env: Env) -> Stats {
+        let storage = env.storage().instance();
+        Stats {
+            total_created: storage.get(&DataKey::Counter).unwrap_or(0),
+            total_settled: storage.get(&DataKey::SettledCount).unwrap_or(0),
+            total_volume: storage.get(&DataKey::TotalVolume).unwrap_or(0),
+            active_count: storage.get(&DataKey::ActiveCount).unwrap_or(0),
+            total_bidders: storage.get(&DataKey::TotalBidders).unwrap_or(0),
+        }
+    }
+
+    /// Cumulative proceeds settled through the contract, for dashboards.
+    pub fn get_total_volume(env: Env) -> i128 {
+        env.storage()
+            .instance()
+            .get(&DataKey::TotalVolume)
+            .unwrap_or(0)
+    }
+
+    /// Reconciliation view: every obligation the contract's balances must
+    /// cover, bucketed by purpose. Iterates all auctions, so this is meant
+    /// for off-chain audit tooling rather than the hot path.
+    pub fn get_escrow_breakdown(env: Env) -> Result<EscrowBreakdown, AuctionError> {
+        let count: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::Counter)
+            .unwrap_or(0);
+        let mut breakdown = EscrowBreakdown {
+            asset_locked: 0,
+            highest_bid_held: 0,
+            pending_refunds_total: 0,
+            deposits_total: 0,
+            accrued_fees: 0,
+        };
+        for id in 0..count {
+            let auction = match env
+                .storage()
+                .persistent()
+                .get::<_, Auction>(&DataKey::Auction(id))
+            {
+                Some(auction) => auction,
+                None => continue,
+            };
+            breakdown.asset_locked += auction.remaining;
+            if !settled(&env, id) {
+                breakdown.highest_bid_held += auction.highest_bid + auction.proceeds;
+                if auction.params.kind == AuctionKind::SplitTopTwo {
+                    breakdown.highest_bid_held += auction.second_bid;
+                }
+            }
+            let depositors: Vec<Address> = env
+                .storage()
+                .persistent()
+                .get(&DataKey::Depositors(id))
+                .unwrap_or_else(|| Vec::new(&env));
+            for who in depositors.iter() {
+                breakdown.deposits_total += env
+                    .storage()
+                    .persistent()
+                    .get(&DataKey::Deposit(id, who))
+                    .unwrap_or(0i128);
+            }
+        }
+        let (collector, _) = fee_config(&env)?;
+        let addrs: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::PendingAddrs)
+            .unwrap_or_else(|| Vec::new(&env));
+        for who in addrs.iter() {
+            let balances: Map<Address, i128> = env
+                .storage()
+                .persistent()
+                .get(&DataKey::PendingRefund(who.clone()))
+                .unwrap_or_else(|| Map::new(&env));
+            let mut total = 0i128;
+            for (_, amount) in balances.iter() {
+                total += amount;
+            }
+            if who == collector {
+                breakdown.accrued_fees += total;
+            } else {
+                breakdown.pending_refunds_total += total;
+            }
+        }
+        Ok(breakdown)
+    }
+
+    /// Ids of live auctions whose (possibly extended) end falls at or before
+    /// `timestamp`, in id order — the "ending soon" shelf. Capped at 100
+    /// entries.
+    pub fn list_ending_before(env: Env, timestamp: u64) -> Vec<u64> {
+        let count: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::Counter)
+            .unwrap_or(0);
+        let mut ids = Vec::new(&env);
+        for id in 0..count {
+            if ids.len() >= 100 {
+                break;
+            }
+            if settled(&env, id) {
+                continue;
+            }
+            if let Some(auction) = env
+                .storage()
+                .persistent()
+                .get::<_, Auction>(&DataKey::Auction(id))
+            {
+                if auction.end_time <= timestamp {
+                    ids.push_back(id);
+                }
+            }
+        }
+        ids
+    }
+
+    /// Page through the outcomes of every settled auction, in id order. The
+    /// page size is capped at 100; an offset past the end yields an empty
+    /// page.
+    pub fn list_outcomes(env: Env, offset: u32, limit: u32) -> Vec<Outcome> {
+        let count: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::Counter)
+            .unwrap_or(0);
+        let limit = limit.min(100);
+        let mut page = Vec::new(&env);
+        let mut skipped: u32 = 0;
+        for id in 0..count {
+            if page.len() >= limit {
+                break;
+            }
+            if let Some(outcome) = env
+                .storage()
+                .persistent()
+                .get::<_, Outcome>(&DataKey::Outcome(id))
+            {
+                if skipped < offset {
+                    skipped += 1;
+                } else {
+                    page.push_back(outcome);
+                }
+            }
+        }
+        page
+    }
+
+    /// Page through the ids of every auction a seller has created.
+    pub fn list_by_seller(env: Env, seller: Address, offset: u32, limit: u32) -> Vec<u64> {
+        let ids: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::SellerAuctions(seller))
+            .unwrap_or_else(|| Vec::new(&env));
+        let mut page = Vec::new(&env);
+        let end = offset.saturating_add(limit).min(ids.len());
+        for i in offset..end {
+            page.push_back(ids.get_unchecked(i));
+        }
+        page
+    }
+
+    /// The price a settled auction closed at, straight off its immutable
+    /// `Outcome` — still answerable after `cleanup` has dropped the heavy
+    /// per-auction state. 0 for no-sales.
+    pub fn get_final_price(env: Env, id: u64) -> Result<i128, AuctionError> {
+        let outcome: Outcome = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Outcome(id))
+            .ok_or(AuctionError::AuctionNotFound)?;
+        Ok(outcome.final_price)
+    }
+
+    /// Who won a settled auction, from the same post-`cleanup` record.
+    /// `None` for no-sales and cancellations.
+    pub fn get_winner(env: Env, id: u64) -> Result<Option<Address>, AuctionError> {
+        let outcome: Outcome = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Outcome(id))
+            .ok_or(AuctionError::AuctionNotFound)?;
+        Ok(outcome.winner)
+    }
+
+    /// Every auction created under `category`, in creation order. Unknown
+    /// categories come back empty.
+    pub fn list_by_category(env: Env, category: Symbol) -> Vec<u64> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Category(category))
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    /// Current leader and amount. With `hide_highest` set, the amount stays
+    /// `None` until the auction ends or settles.
+    pub fn get_highest_bid(env: Env, id: u64) -> Result<HighestBidView, AuctionError> {
+        let auction = read_auction(&env, id)?;
+        let live = env.ledger().timestamp() < auction.end_time && !settled(&env, id);
+        let amount = if auction.highest_bidder.is_none()
+            || (auction.params.hide_highest && live)
+        {
+            None
+        } else {
+            Some(auction.highest_bid)
+        };
+        Ok(HighestBidView {
+            has_bid: auction.highest_bidder.is_some(),
+            bidder: auction.highest_bidder,
+            amount,
+        })
+    }
+
+    /// One-call bundle of the stored record plus the derived views
+    /// (`is_settled`, `get_highest_bid`, `get_progress_bps`,
+    /// `is_reserve_met`) and the current bid floor, all as of the same
+    /// ledger read.
+    pub fn snapshot(env: Env, id: u64) -> Result<AuctionSnapshot, AuctionError> {
+        let auction = read_auction(&env, id)?;
+        let highest = Self::get_highest_bid(env.clone(), id)?;
+        let progress_bps = Self::get_progress_bps(env.clone(), id)?;
+        let now = env.ledger().timestamp();
+        Ok(AuctionSnapshot {
+            settled: settled(&env, id),
+            highest,
+            time_remaining: auction.end_time.saturating_sub(now),
+            progress_bps,
+            reserve_met: auction.highest_bidder.is_some()
+                && auction.highest_bid >= effective_reserve(&auction.params),
+            minimum_next_bid: bid_minimum(&env, &auction)?,
+            auction,
+        })
+    }
+
+    /// How much of `asset_amount` is still escrowed and unallocated —
+    /// essential for relisting divisible leftovers.
+    pub fn get_remaining_asset(env: Env, id: u64) -> Result<i128, AuctionError> {
+        Ok(read_auction(&env, id)?.remaining)
+    }
+
+    /// The auction's current end time. Anti-snipe and manual extensions move
+    /// it, so clients must read it fresh rather than caching the original.
+    pub fn get_end_time(env: Env, id: u64) -> Result<u64, AuctionError> {
+        Ok(read_auction(&env, id)?.end_time)
+    }
+
+    /// Countdown to the scheduled start; zero once bidding is open.
+    pub fn get_time_until_start(env: Env, id: u64) -> Result<u64, AuctionError> {
+        let auction = read_auction(&env, id)?;
+        Ok(auction.start_time.saturating_sub(env.ledger().timestamp()))
+    }
+
+    /// How far through the bidding window the auction is, in basis points,
+    /// clamped to 10_000 once past the (possibly extended) end.
+    pub fn get_progress_bps(env: Env, id: u64) -> Result<u32, AuctionError> {
+        let auction = read_auction(&env, id)?;
+        let now = env.ledger().timestamp();
+        if now <= auction.start_time {
+            return Ok(0);
+        }
+        if now >= auction.end_time {
+            return Ok(10_000);
+        }
+        let window = u128::from(auction.end_time - auction.start_time);
+        let elapsed = u128::from(now - auction.start_time);
+        Ok((elapsed * 10_000 / window) as u32)
+    }
+
+    /// Raw settled flag for an auction, exactly as stored — saves callers
+    /// fetching the whole record just to check one bit.
+    pub fn is_settled(env: Env, id: u64) -> Result<bool, AuctionError> {
+        read_auction(&env, id)?;
+        Ok(settled(&env, id))
+    }
+
+    /// The next meaningful timestamp in the auction's lifecycle with a label
+    /// for what happens then: `start` (bidding opens), `end` (bidding
+    /// closes), `settle` (settlement becomes eligible), `claim` (the winner's
+    /// claim window lapses), or `done` with 0 once nothing is left.
+    pub fn next_transition(env: Env, id: u64) -> Result<(Symbol, u64), AuctionError> {
+        let auction = read_auction(&env, id)?;
+        let now = env.ledger().timestamp();
+        if now < auction.start_time {
+            return Ok((symbol_short!("start"), auction.start_time));
+        }
+        if now < auction.end_time {
+            return Ok((symbol_short!("end"), auction.end_time));
+        }
+        if !settled(&env, id) {
+            let eligible_at = compute_end_time(auction.end_time, auction.params.timing.settle_delay)?;
+            return Ok((symbol_short!("settle"), eligible_at));
+        }
+        if auction.params.timing.claim_deadline > 0 && auction.remaining > 0 {
+            let deadline =
+                compute_end_time(auction.settled_at, auction.params.timing.claim_deadline)?;
+            if now < deadline {
+                return Ok((symbol_short!("claim"), deadline));
+            }
+        }
+        Ok((symbol_short!("done"), 0))
+    }
+
+    /// Seconds since the last accepted bid, or `None` before any bid — the
+    /// live counterpart of the quiet-period settle gate.
+    pub fn time_since_last_bid(env: Env, id: u64) -> Result<Option<u64>, AuctionError> {
+        let auction = read_auction(&env, id)?;
+        if auction.last_bid_time == 0 {
+            return Ok(None);
+        }
+        Ok(Some(
+            env.ledger().timestamp().saturating_sub(auction.last_bid_time),
+        ))
+    }
+
+    /// Whether a bid landing at `at_timestamp` would fall inside the
+    /// anti-snipe window and push the end time out, so front-ends can warn
+    /// before submitting. Assumes a bid that qualifies for extension — for
+    /// `above_reserve_only` auctions that means one clearing the reserve.
+    pub fn would_extend(env: Env, id: u64, at_timestamp: u64) -> Result<bool, AuctionError> {
+        let auction = read_auction(&env, id)?;
+        Ok(auction.params.anti_snipe.window > 0
+            && at_timestamp >= auction.start_time
+            && at_timestamp < auction.end_time
+            && auction.end_time - at_timestamp <= auction.params.anti_snipe.window)
+    }
+
+    /// Whether the current highest bid meets the effective reserve, for
+    /// front-end "reserve met" indicators.
+    pub fn is_reserve_met(env: Env, id: u64) -> Result<bool, AuctionError> {
+        let auction = read_auction(&env, id)?;
+        Ok(auction.highest_bidder.is_some()
+            && auction.highest_bid >= effective_reserve(&auction.params))
+    }
+
+    /// Current decayed price of a Dutch auction.
+    pub fn current_price(env: Env, id: u64) -> Result<i128, AuctionError> {
+        let auction = read_auction(&env, id)?;
+        if auction.params.kind != AuctionKind::Dutch {
+            return Err(AuctionError::WrongKind);
+        }
+        Ok(dutch_price(&auction, env.ledger().timestamp()))
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,3419 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::testutils::{Address as _, Events as _, Ledger};
+use soroban_sdk::{token, vec, Address, Env, IntoVal, Val};
+
+pub(crate) struct Setup {
+    pub env: Env,
+    pub fee_collector: Address,
+    pub seller: Address,
+    pub contract_id: Address,
+    pub client: AuctionContractClient<'static>,
+    pub asset: token::Client<'static>,
+    pub bid_token: token::Client<'static>,
+    pub bid_admin: token::StellarAssetClient<'static>,
+}
+
+pub(crate) fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let fee_collector = Address::generate(&env);
+    let seller = Address::generate(&env);
+    let contract_id = env.register(AuctionContract, ());
+    let client = AuctionContractClient::new(&env, &contract_id);
+    // 2% protocol fee throughout the tests.
+    client.initialize(&admin, &fee_collector, &200);
+
+    let asset_sac = env.register_stellar_asset_contract_v2(admin.clone());
+    let bid_sac = env.register_stellar_asset_contract_v2(admin.clone());
+    let asset = token::Client::new(&env, &asset_sac.address());
+    let asset_admin = token::StellarAssetClient::new(&env, &asset_sac.address());
+    let bid_token = token::Client::new(&env, &bid_sac.address());
+    let bid_admin = token::StellarAssetClient::new(&env, &bid_sac.address());
+    asset_admin.mint(&seller, &1_000_000);
+
+    Setup {
+        env,
+        fee_collector,
+        seller,
+        contract_id,
+        client,
+        asset,
+        bid_token,
+        bid_admin,
+    }
+}
+
+pub(crate) fn default_params(s: &Setup) -> AuctionParams {
+    AuctionParams {
+        kind: AuctionKind::English,
+        category: Symbol::new(&s.env, ""),
+        seller: s.seller.clone(),
+        co_sellers: Vec::new(&s.env),
+        asset: s.asset.address.clone(),
+        asset_amount: 1,
+        bid_token: s.bid_token.address.clone(),
+        reserve_price: 100,
+        reserve_ratio: 0,
+        twap: TwapConfig {
+            oracle: None,
+            reserve: 0,
+            max_age: 0,
+        },
+        soft_reserve: 0,
+        buy_now_price: 0,
+        dutch_step: DutchStep {
+            interval: 0,
+            amount: 0,
+        },
+        start_time: 0,
+        duration: 1_000,
+        min_increment: 10,
+        increment_schedule: Vec::new(&s.env),
+        opening_bid_bps: 0,
+        bid_tick: 0,
+        retraction_penalty: 0,
+        anti_snipe: AntiSnipeConfig {
+            window: 0,
+            period: 0,
+            above_reserve_only: false,
+        },
+        timing: TimingConfig {
+            settle_delay: 0,
+            quiet_period: 0,
+            claim_deadline: 0,
+        },
+        installments: InstallmentPlan {
+            down_bps: 0,
+            deadline: 0,
+            refund_bps: 0,
+        },
+        refund_mode: RefundMode::Immediate,
+        hide_highest: false,
+        deposit: 0,
+        royalty: RevenueShare {
+            recipient: None,
+            bps: 0,
+        },
+        donation: RevenueShare {
+            recipient: None,
+            bps: 0,
+        },
+        burn_bps: 0,
+        buyer_premium_bps: 0,
+        relist: RelistConfig {
+            auto: false,
+            count: 0,
+        },
+        mint_on_settle: false,
+        whole_units_only: false,
+        tie_break: TieBreak::FirstBid,
+        escrow_address: None,
+        minimum_escalation: 0,
+        freeze_clock_on_pause: false,
+        max_escrow: None,
+        whitelist: Vec::new(&s.env),
+        bundle: Vec::new(&s.env),
+    }
+}
+
+pub(crate) fn fund_bidder(s: &Setup, amount: i128) -> Address {
+    let bidder = Address::generate(&s.env);
+    s.bid_admin.mint(&bidder, &amount);
+    bidder
+}
+
+pub(crate) fn set_time(env: &Env, timestamp: u64) {
+    env.ledger().with_mut(|l| l.timestamp = timestamp);
+}
+
+/// Number of events from the last invocation published by the auction
+/// contract under exactly `topics`.
+pub(crate) fn event_count(s: &Setup, topics: Vec<Val>) -> usize {
+    s.env
+        .events()
+        .all()
+        .iter()
+        .filter(|e| e.0 == s.contract_id && e.1 == topics)
+        .count()
+}
+
+/// Escrow accounting invariant: for the given auctions, the contract's
+/// bid-token balance must equal the live bid escrow (highest bid plus
+/// divisible proceeds plus deposits) and its asset balance the unallocated
+/// units, each plus whatever is parked as pending claims in that token.
+/// Call it after any sequence of operations that moves value.
+pub(crate) fn assert_escrow_invariants(s: &Setup, ids: &[u64]) {
+    let (expected_bid, expected_asset) = s.env.as_contract(&s.contract_id, || {
+        let storage = s.env.storage().persistent();
+        let mut bid = 0i128;
+        let mut asset = 0i128;
+        for &id in ids {
+            let auction: Auction = storage.get(&DataKey::Auction(id)).unwrap();
+            let is_settled: bool = storage.get(&DataKey::IsSettle(id)).unwrap_or(false);
+            if !is_settled {
+                bid += auction.highest_bid + auction.proceeds;
+                if auction.params.kind == AuctionKind::SplitTopTwo {
+                    bid += auction.second_bid;
+                }
+            }
+            asset += auction.remaining;
+            let depositors: Vec<Address> = storage
+                .get(&DataKey::Depositors(id))
+                .unwrap_or_else(|| Vec::new(&s.env));
+            for who in depositors.iter() {
+                bid += storage.get(&DataKey::Deposit(id, who)).unwrap_or(0i128);
+            }
+        }
+        let addrs: Vec<Address> = storage
+            .get(&DataKey::PendingAddrs)
+            .unwrap_or_else(|| Vec::new(&s.env));
+        for who in addrs.iter() {
+            let balances: Map<Address, i128> = storage
+                .get(&DataKey::PendingRefund(who))
+                .unwrap_or_else(|| Map::new(&s.env));
+            bid += balances.get(s.bid_token.address.clone()).unwrap_or(0);
+            asset += balances.get(s.asset.address.clone()).unwrap_or(0);
+        }
+        (bid, asset)
+    });
+    assert_eq!(s.bid_token.balance(&s.contract_id), expected_bid);
+    assert_eq!(s.asset.balance(&s.contract_id), expected_asset);
+}
+
+/// Anti-snipe probe: jump to exactly `offset_before_end` seconds before the
+/// auction's current end, place `amount` for `bidder`, and assert the end
+/// time that results — pinning down the window's boundary behavior.
+pub(crate) fn bid_at_offset_and_assert_end(
+    s: &Setup,
+    id: u64,
+    bidder: &Address,
+    amount: i128,
+    offset_before_end: u64,
+    expected_end: u64,
+) {
+    let end = s.client.get_end_time(&id);
+    set_time(&s.env, end - offset_before_end);
+    s.client.place_bid(&id, bidder, &amount, &None);
+    assert_eq!(s.client.get_end_time(&id), expected_end);
+}
+
+mod mock_oracle {
+    use soroban_sdk::{contract, contractimpl, Env};
+
+    #[contract]
+    pub struct MockOracle;
+
+    #[contractimpl]
+    impl MockOracle {
+        /// 2.5 reference units per bid token, 7 decimals.
+        pub fn price(_env: Env) -> (i128, u32) {
+            (25_000_000, 7)
+        }
+    }
+}
+
+mod mock_twap {
+    use soroban_sdk::{contract, contractimpl, contracttype, Env};
+
+    #[contracttype]
+    pub enum Key {
+        Obs,
+    }
+
+    /// Settable time-weighted price source: `(price, decimals, timestamp)`.
+    #[contract]
+    pub struct MockTwap;
+
+    #[contractimpl]
+    impl MockTwap {
+        pub fn set(env: Env, price: i128, decimals: u32, timestamp: u64) {
+            env.storage()
+                .persistent()
+                .set(&Key::Obs, &(price, decimals, timestamp));
+        }
+
+        pub fn twap(env: Env) -> (i128, u32, u64) {
+            env.storage().persistent().get(&Key::Obs).unwrap()
+        }
+    }
+}
+
+/// A stand-in for a contract-held treasury (e.g. a DAO) acting as seller:
+/// it has no methods of its own, it just needs to be a contract address that
+/// can authorize and hold token balances.
+mod mock_treasury {
+    use soroban_sdk::contract;
+
+    #[contract]
+    pub struct MockTreasury;
+}
+
+/// Minimal NFT contract for lazy-mint settlement: `mint` records the owner
+/// of a token id.
+mod mock_nft {
+    use soroban_sdk::{contract, contractimpl, contracttype, Address, Env};
+
+    #[contracttype]
+    pub enum Key {
+        Owner(i128),
+    }
+
+    #[contract]
+    pub struct MockNft;
+
+    #[contractimpl]
+    impl MockNft {
+        pub fn mint(env: Env, to: Address, token_id: i128) {
+            env.storage().persistent().set(&Key::Owner(token_id), &to);
+        }
+
+        pub fn owner_of(env: Env, token_id: i128) -> Option<Address> {
+            env.storage().persistent().get(&Key::Owner(token_id))
+        }
+    }
+}
+
+mod mock_kyc {
+    use soroban_sdk::{contract, contractimpl, contracttype, Address, Env};
+
+    #[contracttype]
+    pub enum Key {
+        Verified(Address),
+    }
+
+    #[contract]
+    pub struct MockKyc;
+
+    #[contractimpl]
+    impl MockKyc {
+        pub fn set_verified(env: Env, who: Address, verified: bool) {
+            env.storage().persistent().set(&Key::Verified(who), &verified);
+        }
+
+        pub fn is_verified(env: Env, who: Address) -> bool {
+            env.storage().persistent().get(&Key::Verified(who)).unwrap_or(false)
+        }
+    }
+}
+
+mod mock_reentrant {
+    use soroban_sdk::{contract, contractimpl, contracttype, vec, Address, Env, IntoVal, Symbol, Val};
+
+    #[contracttype]
+    pub enum Key {
+        Balance(Address),
+        /// Auction contract the token re-enters.
+        Target,
+        /// Credits to this address trigger the re-entry.
+        Armed(Address),
+        /// Whether the nested `withdraw` ever succeeded.
+        Reentered,
+    }
+
+    /// Malicious token: crediting the armed address re-enters the auction's
+    /// `withdraw` before the credit lands, probing for double-withdraws.
+    #[contract]
+    pub struct ReentrantToken;
+
+    #[contractimpl]
+    impl ReentrantToken {
+        pub fn mint(env: Env, to: Address, amount: i128) {
+            let held = Self::balance(env.clone(), to.clone());
+            env.storage().persistent().set(&Key::Balance(to), &(held + amount));
+        }
+
+        pub fn arm(env: Env, target: Address, who: Address) {
+            env.storage().persistent().set(&Key::Target, &target);
+            env.storage().persistent().set(&Key::Armed(who), &true);
+        }
+
+        pub fn reentry_succeeded(env: Env) -> bool {
+            env.storage().persistent().get(&Key::Reentered).unwrap_or(false)
+        }
+
+        pub fn balance(env: Env, id: Address) -> i128 {
+            env.storage().persistent().get(&Key::Balance(id)).unwrap_or(0)
+        }
+
+        pub fn transfer(env: Env, from: Address, to: Address, amount: i128) {
+            from.require_auth();
+            let from_held = Self::balance(env.clone(), from.clone());
+            if from_held < amount {
+                panic!("insufficient balance");
+            }
+            env.storage()
+                .persistent()
+                .set(&Key::Balance(from), &(from_held - amount));
+            let armed: bool = env
+                .storage()
+                .persistent()
+                .get(&Key::Armed(to.clone()))
+                .unwrap_or(false);
+            if armed {
+                // Disarm first so a successful re-entry can't recurse.
+                env.storage().persistent().set(&Key::Armed(to.clone()), &false);
+                let target: Address = env.storage().persistent().get(&Key::Target).unwrap();
+                let nested = env.try_invoke_contract::<Val, soroban_sdk::Error>(
+                    &target,
+                    &Symbol::new(&env, "withdraw"),
+                    vec![&env, to.into_val(&env)],
+                );
+                if nested.is_ok() {
+                    env.storage().persistent().set(&Key::Reentered, &true);
+                }
+            }
+            let to_held = Self::balance(env.clone(), to.clone());
+            env.storage()
+                .persistent()
+                .set(&Key::Balance(to), &(to_held + amount));
+        }
+    }
+}
+
+mod mock_token {
+    use soroban_sdk::{contract, contractimpl, contracttype, Address, Env};
+
+    #[contracttype]
+    pub enum Key {
+        Balance(Address),
+        Blocked(Address),
+        Skimmed(Address),
+        Fee,
+    }
+
+    /// Token that rejects transfers to "blocked" addresses, standing in for
+    /// recipients without a trustline.
+    #[contract]
+    pub struct MockToken;
+
+    #[contractimpl]
+    impl MockToken {
+        pub fn mint(env: Env, to: Address, amount: i128) {
+            let held = Self::balance(env.clone(), to.clone());
+            env.storage().persistent().set(&Key::Balance(to), &(held + amount));
+        }
+
+        pub fn set_blocked(env: Env, who: Address, blocked: bool) {
+            env.storage().persistent().set(&Key::Blocked(who), &blocked);
+        }
+
+        /// Transfers to a "skimmed" address debit the sender but credit
+        /// nothing, modeling fee-on-transfer misbehavior.
+        pub fn set_skimmed(env: Env, who: Address, skimmed: bool) {
+            env.storage().persistent().set(&Key::Skimmed(who), &skimmed);
+        }
+
+        /// Flat fee deducted from what the recipient is credited.
+        pub fn set_fee(env: Env, fee: i128) {
+            env.storage().persistent().set(&Key::Fee, &fee);
+        }
+
+        pub fn clawback(env: Env, from: Address, amount: i128) {
+            let held = Self::balance(env.clone(), from.clone());
+            env.storage()
+                .persistent()
+                .set(&Key::Balance(from), &(held - amount));
+        }
+
+        pub fn balance(env: Env, id: Address) -> i128 {
+            env.storage().persistent().get(&Key::Balance(id)).unwrap_or(0)
+        }
+
+        pub fn transfer(env: Env, from: Address, to: Address, amount: i128) {
+            from.require_auth();
+            if env
+                .storage()
+                .persistent()
+                .get(&Key::Blocked(to.clone()))
+                .unwrap_or(false)
+            {
+                panic!("no trustline");
+            }
+            let from_held = Self::balance(env.clone(), from.clone());
+            if from_held < amount {
+                panic!("insufficient balance");
+            }
+            env.storage()
+                .persistent()
+                .set(&Key::Balance(from), &(from_held - amount));
+            if env
+                .storage()
+                .persistent()
+                .get(&Key::Skimmed(to.clone()))
+                .unwrap_or(false)
+            {
+                return;
+            }
+            let fee: i128 = env.storage().persistent().get(&Key::Fee).unwrap_or(0);
+            let to_held = Self::balance(env.clone(), to.clone());
+            env.storage()
+                .persistent()
+                .set(&Key::Balance(to), &(to_held + amount - fee));
+        }
+    }
+}
+
+#[test]
+fn settle_finalizes_when_seller_cannot_receive() {
+    let s = setup();
+    let mock = s.env.register(mock_token::MockToken, ());
+    let mock_client = mock_token::MockTokenClient::new(&s.env, &mock);
+
+    let mut params = default_params(&s);
+    params.bid_token = mock.clone();
+    let id = s.client.create_auction(&params);
+    let alice = Address::generate(&s.env);
+    mock_client.mint(&alice, &500);
+    s.client.place_bid(&id, &alice, &100, &None);
+
+    // The seller loses the ability to receive the bid token before settle.
+    mock_client.set_blocked(&s.seller, &true);
+    set_time(&s.env, 1_000);
+    s.client.settle(&id);
+    // Settlement finalized: the winner has the asset, the seller's share is
+    // parked as a pending claim.
+    assert_eq!(s.asset.balance(&alice), 1);
+    assert_eq!(mock_client.balance(&s.seller), 0);
+    assert_eq!(mock_client.balance(&s.fee_collector), 2);
+
+    // Once the trustline exists again the funds are withdrawable.
+    mock_client.set_blocked(&s.seller, &false);
+    s.client.withdraw(&s.seller);
+    assert_eq!(mock_client.balance(&s.seller), 98);
+}
+
+#[test]
+fn escrow_invariants_hold_across_mixed_auction_activity() {
+    let s = setup();
+    // A deposit-taking pull-mode English auction and a divisible lot running
+    // side by side, sharing the same bid token.
+    let mut english = default_params(&s);
+    english.refund_mode = RefundMode::Pull;
+    english.deposit = 25;
+    let english_id = s.client.create_auction(&english);
+    let mut divisible = default_params(&s);
+    divisible.kind = AuctionKind::Divisible;
+    divisible.asset_amount = 50;
+    divisible.reserve_price = 2;
+    let divisible_id = s.client.create_auction(&divisible);
+    let ids = [english_id, divisible_id];
+
+    let alice = fund_bidder(&s, 1_000);
+    let bob = fund_bidder(&s, 1_000);
+    s.client.place_bid(&english_id, &alice, &100, &None);
+    assert_escrow_invariants(&s, &ids);
+    s.client.place_bid(&english_id, &bob, &120, &None);
+    assert_escrow_invariants(&s, &ids);
+    s.client.buy_units(&divisible_id, &alice, &20);
+    assert_escrow_invariants(&s, &ids);
+    s.client.place_bid(&english_id, &alice, &150, &None);
+    assert_escrow_invariants(&s, &ids);
+
+    set_time(&s.env, 1_000);
+    s.client.settle(&english_id);
+    assert_escrow_invariants(&s, &ids);
+    s.client.settle(&divisible_id);
+    assert_escrow_invariants(&s, &ids);
+    s.client.withdraw(&alice);
+    assert_escrow_invariants(&s, &ids);
+}
+
+#[test]
+fn burn_bps_removes_a_share_of_proceeds_from_circulation() {
+    let s = setup();
+    let mut params = default_params(&s);
+    // 2% fee + 5% burn on a 1_000 hammer price.
+    params.burn_bps = 500;
+    let id = s.client.create_auction(&params);
+    let alice = fund_bidder(&s, 1_000);
+    s.client.place_bid(&id, &alice, &1_000, &None);
+
+    set_time(&s.env, 1_000);
+    s.client.settle(&id);
+    assert_eq!(s.bid_token.balance(&s.fee_collector), 20);
+    assert_eq!(s.bid_token.balance(&s.seller), 930);
+    // The burned 50 is gone: no balance anywhere accounts for it.
+    assert_eq!(s.bid_token.balance(&s.contract_id), 0);
+}
+
+#[test]
+fn burn_bps_cannot_push_total_splits_past_100_percent() {
+    let s = setup();
+    let mut params = default_params(&s);
+    params.royalty = RevenueShare {
+        recipient: Some(Address::generate(&s.env)),
+        bps: 5_000,
+    };
+    params.burn_bps = 4_900;
+    assert_eq!(
+        s.client.try_create_auction(&params),
+        Err(Ok(AuctionError::InvalidParams))
+    );
+}
+
+#[test]
+fn end_time_writes_reject_degenerate_windows() {
+    let s = setup();
+    let mut auction = Auction {
+        params: default_params(&s),
+        start_time: 5_000,
+        end_time: 6_000,
+        highest_bidder: None,
+        highest_bid: 0,
+        refund_to: None,
+        second_bidder: None,
+        second_bid: 0,
+        last_bid_time: 0,
+        settled_at: 0,
+        remaining: 1,
+        proceeds: 0,
+        relists_done: 0,
+        seller_paused_at: 0,
+        distinct_bidders: 0,
+    };
+    assert_eq!(
+        apply_end_time(&mut auction, 4_000),
+        Err(AuctionError::InvalidTimeRange)
+    );
+    assert_eq!(
+        apply_end_time(&mut auction, 5_000),
+        Err(AuctionError::InvalidTimeRange)
+    );
+    // The rejected writes left the window untouched.
+    assert_eq!(auction.end_time, 6_000);
+    assert_eq!(apply_end_time(&mut auction, 7_000), Ok(()));
+    assert_eq!(auction.end_time, 7_000);
+}
+
+#[test]
+fn auto_relist_reopens_no_sales_until_the_budget_runs_out() {
+    let s = setup();
+    let mut params = default_params(&s);
+    params.relist = RelistConfig { auto: true, count: 2 };
+    let id = s.client.create_auction(&params);
+
+    set_time(&s.env, 1_000);
+    s.client.settle(&id);
+    assert_eq!(
+        event_count(&s, (symbol_short!("relisted"), id).into_val(&s.env)),
+        1
+    );
+    // The asset stays escrowed and the fresh window must run its course.
+    assert_eq!(s.asset.balance(&s.seller), 1_000_000 - 1);
+    assert_eq!(
+        s.client.try_settle(&id),
+        Err(Ok(AuctionError::AuctionNotEnded))
+    );
+    assert_eq!(s.client.get_end_time(&id), 2_000);
+
+    set_time(&s.env, 2_000);
+    s.client.settle(&id);
+    assert_eq!(s.client.get_end_time(&id), 3_000);
+
+    // Both relists spent: the third no-sale settles for real.
+    set_time(&s.env, 3_000);
+    s.client.settle(&id);
+    assert_eq!(s.asset.balance(&s.seller), 1_000_000);
+}
+
+#[test]
+fn reserve_ratio_scales_the_floor_with_the_lot_size() {
+    let s = setup();
+    let alice = fund_bidder(&s, 10_000);
+    // 3 bid units per asset unit: a 50-unit lot needs a 150 opening bid,
+    // a 200-unit lot needs 600.
+    let mut params = default_params(&s);
+    params.reserve_ratio = 3;
+    params.asset_amount = 50;
+    let small = s.client.create_auction(&params);
+    params.asset_amount = 200;
+    let large = s.client.create_auction(&params);
+
+    assert_eq!(
+        s.client.try_place_bid(&small, &alice, &149, &None),
+        Err(Ok(AuctionError::BidTooLow))
+    );
+    s.client.place_bid(&small, &alice, &150, &None);
+    assert!(s.client.is_reserve_met(&small));
+
+    assert_eq!(
+        s.client.try_place_bid(&large, &alice, &599, &None),
+        Err(Ok(AuctionError::BidTooLow))
+    );
+    s.client.place_bid(&large, &alice, &600, &None);
+}
+
+#[test]
+fn version_and_features_report_capabilities() {
+    let s = setup();
+    assert_eq!(s.client.version(), (0, 1, 0));
+    let features = s.client.features();
+    assert!(features.contains(symbol_short!("english")));
+    assert!(features.contains(symbol_short!("antisnipe")));
+    assert!(!features.contains(symbol_short!("kyc")));
+
+    // The KYC gate only shows up once it's actually configured.
+    let kyc = s.env.register(mock_kyc::MockKyc, ());
+    s.client.set_kyc_contract(&Some(kyc));
+    assert!(s.client.features().contains(symbol_short!("kyc")));
+}
+
+#[test]
+fn bid_event_carries_amount_highest_flag_and_rank() {
+    let s = setup();
+    let id = s.client.create_auction(&default_params(&s));
+    let alice = fund_bidder(&s, 500);
+    let bob = fund_bidder(&s, 500);
+    s.client.place_bid(&id, &alice, &100, &None);
+    s.client.place_bid(&id, &bob, &130, &None);
+
+    let events = s.env.events().all();
+    let (contract, topics, data) = events.last_unchecked();
+    assert_eq!(contract, s.contract_id);
+    assert_eq!(topics, (symbol_short!("bid"), id).into_val(&s.env));
+    let (bidder, amount, new_highest, rank): (Address, i128, bool, u32) =
+        data.into_val(&s.env);
+    assert_eq!(bidder, bob);
+    assert_eq!(amount, 130);
+    assert!(new_highest);
+    assert_eq!(rank, 1);
+}
+
+#[test]
+fn settle_refunds_winner_when_the_escrowed_asset_is_gone() {
+    let s = setup();
+    let mock = s.env.register(mock_token::MockToken, ());
+    let mock_client = mock_token::MockTokenClient::new(&s.env, &mock);
+    mock_client.mint(&s.seller, &10);
+
+    let mut params = default_params(&s);
+    params.asset = mock.clone();
+    params.asset_amount = 10;
+    let id = s.client.create_auction(&params);
+    let alice = fund_bidder(&s, 500);
+    s.client.place_bid(&id, &alice, &100, &None);
+
+    // The asset vanishes from escrow behind the contract's back.
+    mock_client.clawback(&s.contract_id, &10);
+    set_time(&s.env, 1_000);
+    s.client.settle(&id);
+
+    // Settled as a refund: Alice is made whole, nobody is paid.
+    assert_eq!(s.bid_token.balance(&alice), 500);
+    assert_eq!(s.bid_token.balance(&s.seller), 0);
+    let outcome = s.client.list_outcomes(&0, &10).get_unchecked(0);
+    assert_eq!(outcome.winner, None);
+    assert_eq!(
+        s.client.try_settle(&id),
+        Err(Ok(AuctionError::AlreadySettled))
+    );
+}
+
+#[test]
+fn drained_divisible_auction_still_pays_out_completed_fills() {
+    let s = setup();
+    let mock = s.env.register(mock_token::MockToken, ());
+    let mock_client = mock_token::MockTokenClient::new(&s.env, &mock);
+    mock_client.mint(&s.seller, &10);
+
+    let mut params = default_params(&s);
+    params.kind = AuctionKind::Divisible;
+    params.asset = mock.clone();
+    params.asset_amount = 10;
+    params.reserve_price = 50; // per unit
+    let id = s.client.create_auction(&params);
+    let alice = fund_bidder(&s, 500);
+    s.client.buy_units(&id, &alice, &4);
+
+    // The unsold remainder vanishes from escrow.
+    mock_client.clawback(&s.contract_id, &6);
+    set_time(&s.env, 1_000);
+    s.client.settle(&id);
+
+    // Alice's fill was delivered at purchase time and stays hers; the 200
+    // of completed sales is split as usual instead of stranding.
+    assert_eq!(mock_client.balance(&alice), 4);
+    assert_eq!(s.bid_token.balance(&s.seller), 196);
+    assert_eq!(s.bid_token.balance(&s.fee_collector), 4);
+    assert_eq!(s.bid_token.balance(&s.contract_id), 0);
+    assert_eq!(s.client.get_final_price(&id), 200);
+}
+
+#[test]
+fn deduction_rounding_conserves_funds_on_awkward_amounts() {
+    let s = setup();
+    let royalty_recipient = Address::generate(&s.env);
+    let mut params = default_params(&s);
+    // 2% fee + 3.33% royalty + 0.77% burn on a prime hammer price: every
+    // share floors and the seller takes the exact remainder.
+    params.royalty = RevenueShare {
+        recipient: Some(royalty_recipient.clone()),
+        bps: 333,
+    };
+    params.burn_bps = 77;
+    params.buyer_premium_bps = 1_000;
+    let id = s.client.create_auction(&params);
+    let alice = fund_bidder(&s, 2_000);
+    s.client.place_bid(&id, &alice, &997, &None);
+    // Escrow holds the bid plus its floored 10% premium.
+    assert_eq!(s.bid_token.balance(&s.contract_id), 997 + 99);
+
+    set_time(&s.env, 1_000);
+    s.client.settle(&id);
+    let fee = 997 * 200 / 10_000; // 19
+    let royalty = 997 * 333 / 10_000; // 33
+    let burn = 997 * 77 / 10_000; // 7
+    assert_eq!(s.bid_token.balance(&s.fee_collector), fee + 99);
+    assert_eq!(s.bid_token.balance(&royalty_recipient), royalty);
+    assert_eq!(s.bid_token.balance(&s.seller), 997 - fee - royalty - burn);
+    // Conservation: everything escrowed was paid out or burned, nothing
+    // remains and nothing was overdrawn.
+    assert_eq!(s.bid_token.balance(&s.contract_id), 0);
+    assert_eq!(
+        s.bid_token.balance(&alice)
+            + s.bid_token.balance(&s.fee_collector)
+            + s.bid_token.balance(&royalty_recipient)
+            + s.bid_token.balance(&s.seller),
+        2_000 - burn
+    );
+}
+
+#[test]
+fn twap_reserve_enforces_reference_value_and_staleness() {
+    let s = setup();
+    let twap = s.env.register(mock_twap::MockTwap, ());
+    let twap_client = mock_twap::MockTwapClient::new(&s.env, &twap);
+    // 0.5 reference units per bid token, 2 decimals, observed at t=0.
+    twap_client.set(&50, &2, &0);
+
+    let mut params = default_params(&s);
+    params.twap.oracle = Some(twap.clone());
+    params.twap.reserve = 90; // 90 reference units => 180 bid tokens.
+    params.twap.max_age = 600;
+    let id = s.client.create_auction(&params);
+    let alice = fund_bidder(&s, 1_000);
+
+    assert_eq!(
+        s.client.try_place_bid(&id, &alice, &179, &None),
+        Err(Ok(AuctionError::BidTooLow))
+    );
+    s.client.place_bid(&id, &alice, &180, &None);
+
+    // Once the observation ages past the limit, bidding is rejected until
+    // the oracle refreshes.
+    let bob = fund_bidder(&s, 1_000);
+    set_time(&s.env, 601);
+    assert_eq!(
+        s.client.try_place_bid(&id, &bob, &200, &None),
+        Err(Ok(AuctionError::StaleOracle))
+    );
+    twap_client.set(&50, &2, &601);
+    s.client.place_bid(&id, &bob, &200, &None);
+}
+
+#[test]
+fn bid_at_buy_now_price_settles_instantly() {
+    let s = setup();
+    let mut params = default_params(&s);
+    // Reserve and buy-now coincide: the first valid bid is a purchase.
+    params.buy_now_price = 100;
+    let id = s.client.create_auction(&params);
+    let alice = fund_bidder(&s, 500);
+
+    s.client.place_bid(&id, &alice, &100, &None);
+    assert_eq!(s.asset.balance(&alice), 1);
+    assert_eq!(s.bid_token.balance(&s.seller), 98);
+    assert_eq!(
+        s.client.try_place_bid(&id, &fund_bidder(&s, 500), &200, &None),
+        Err(Ok(AuctionError::AlreadySettled))
+    );
+}
+
+#[test]
+fn escrow_breakdown_reconciles_with_token_balances() {
+    let s = setup();
+    let mut english = default_params(&s);
+    english.refund_mode = RefundMode::Pull;
+    english.deposit = 25;
+    let english_id = s.client.create_auction(&english);
+    let mut divisible = default_params(&s);
+    divisible.kind = AuctionKind::Divisible;
+    divisible.asset_amount = 40;
+    divisible.reserve_price = 3;
+    let divisible_id = s.client.create_auction(&divisible);
+
+    let alice = fund_bidder(&s, 1_000);
+    let bob = fund_bidder(&s, 1_000);
+    s.client.place_bid(&english_id, &alice, &100, &None);
+    s.client.place_bid(&english_id, &bob, &140, &None);
+    s.client.buy_units(&divisible_id, &alice, &10);
+
+    let breakdown = s.client.get_escrow_breakdown();
+    assert_eq!(breakdown.asset_locked, 1 + 30);
+    assert_eq!(breakdown.highest_bid_held, 140 + 30);
+    assert_eq!(breakdown.pending_refunds_total, 100);
+    assert_eq!(breakdown.deposits_total, 50);
+    assert_eq!(breakdown.accrued_fees, 0);
+    assert_eq!(
+        s.bid_token.balance(&s.contract_id),
+        breakdown.highest_bid_held + breakdown.pending_refunds_total + breakdown.deposits_total
+    );
+    assert_eq!(s.asset.balance(&s.contract_id), breakdown.asset_locked);
+}
+
+#[test]
+fn validate_bid_honors_buy_now_precedence() {
+    let s = setup();
+    let mut params = default_params(&s);
+    params.bid_tick = 50;
+    params.buy_now_price = 375; // deliberately off-tick
+    let id = s.client.create_auction(&params);
+    let alice = fund_bidder(&s, 1_000);
+    let bob = fund_bidder(&s, 1_000);
+
+    // Below the buy-now price the tick check applies as usual...
+    assert_eq!(
+        s.client.try_validate_bid(&id, &alice, &120),
+        Err(Ok(AuctionError::BidNotOnTick))
+    );
+    // ...but an amount reaching it is a purchase, tick or no tick.
+    s.client.validate_bid(&id, &alice, &375);
+    // Still valid with a live leader escalating the minimum, exactly like
+    // the real call, where 375 buys the lot on the spot.
+    s.client.place_bid(&id, &bob, &350, &None);
+    s.client.validate_bid(&id, &alice, &375);
+    s.client.place_bid(&id, &alice, &375, &None);
+    assert!(s.client.is_settled(&id));
+    assert_eq!(s.asset.balance(&alice), 1);
+}
+
+#[test]
+fn reverse_auction_pays_the_lowest_ask_from_the_seller_budget() {
+    let s = setup();
+    // The seller escrows a 500 payment budget alongside the asset.
+    s.bid_admin.mint(&s.seller, &500);
+    let mut params = default_params(&s);
+    params.kind = AuctionKind::Reverse;
+    params.reserve_price = 500;
+    params.min_increment = 50;
+    let id = s.client.create_auction(&params);
+    assert_eq!(s.bid_token.balance(&s.seller), 0);
+
+    let alice = Address::generate(&s.env);
+    let bob = Address::generate(&s.env);
+    // Asks descend: an undercut must clear the increment.
+    s.client.place_bid(&id, &alice, &500, &None);
+    assert_eq!(
+        s.client.try_place_bid(&id, &bob, &460, &None),
+        Err(Ok(AuctionError::BidTooLow))
+    );
+    s.client.place_bid(&id, &bob, &450, &None);
+
+    set_time(&s.env, 1_000);
+    s.client.settle(&id);
+    // Bob takes the asset and his asked payment; the unspent budget
+    // returns to the seller. Alice is owed nothing.
+    assert_eq!(s.asset.balance(&bob), 1);
+    assert_eq!(s.bid_token.balance(&bob), 450);
+    assert_eq!(s.bid_token.balance(&s.seller), 50);
+    assert_eq!(s.bid_token.balance(&alice), 0);
+    assert_eq!(s.bid_token.balance(&s.contract_id), 0);
+}
+
+#[test]
+fn reverse_auction_with_no_takers_returns_asset_and_budget() {
+    let s = setup();
+    s.bid_admin.mint(&s.seller, &500);
+    let mut params = default_params(&s);
+    params.kind = AuctionKind::Reverse;
+    params.reserve_price = 500;
+    let id = s.client.create_auction(&params);
+
+    set_time(&s.env, 1_000);
+    s.client.settle(&id);
+    assert_eq!(s.asset.balance(&s.seller), 1_000_000);
+    assert_eq!(s.bid_token.balance(&s.seller), 500);
+}
+
+#[test]
+fn cancelling_a_reverse_auction_returns_the_budget() {
+    let s = setup();
+    s.bid_admin.mint(&s.seller, &500);
+    let mut params = default_params(&s);
+    params.kind = AuctionKind::Reverse;
+    params.reserve_price = 500;
+    params.start_time = 5_000;
+    let id = s.client.create_auction(&params);
+    assert_eq!(s.bid_token.balance(&s.seller), 0);
+
+    s.client.cancel_before_start(&id);
+    // Asset and payment budget both come home.
+    assert_eq!(s.asset.balance(&s.seller), 1_000_000);
+    assert_eq!(s.bid_token.balance(&s.seller), 500);
+    assert_eq!(s.bid_token.balance(&s.contract_id), 0);
+}
+
+#[test]
+fn asset_missing_close_out_returns_the_reverse_budget() {
+    let s = setup();
+    let mock = s.env.register(mock_token::MockToken, ());
+    let mock_client = mock_token::MockTokenClient::new(&s.env, &mock);
+    s.bid_admin.mint(&s.seller, &500);
+    let mut params = default_params(&s);
+    params.kind = AuctionKind::Reverse;
+    params.reserve_price = 500;
+    params.asset = mock.clone();
+    mock_client.mint(&s.seller, &1);
+    let id = s.client.create_auction(&params);
+    let alice = Address::generate(&s.env);
+    s.client.place_bid(&id, &alice, &450, &None);
+    // The escrowed lot vanishes before settlement.
+    mock_client.clawback(&s.contract_id, &1);
+
+    set_time(&s.env, 1_000);
+    s.client.settle(&id);
+    // The taker escrowed nothing and is owed nothing; the stranded funds
+    // are the seller's payment budget, which comes home.
+    assert_eq!(s.bid_token.balance(&alice), 0);
+    assert_eq!(s.bid_token.balance(&s.seller), 500);
+    assert_eq!(s.bid_token.balance(&s.contract_id), 0);
+}
+
+#[test]
+fn validate_bid_mirrors_place_bid_rejections() {
+    let s = setup();
+    let mut params = default_params(&s);
+    params.bid_tick = 5;
+    params.whitelist = vec![&s.env, Address::generate(&s.env)];
+    let id = s.client.create_auction(&params);
+    let alice = fund_bidder(&s, 1_000);
+
+    // Whitelist, tick, reserve, and timing rejections all match the real
+    // call, with no funds moved.
+    assert_eq!(
+        s.client.try_validate_bid(&id, &alice, &100),
+        Err(Ok(AuctionError::NotWhitelisted))
+    );
+    assert_eq!(
+        s.client.try_place_bid(&id, &alice, &100, &None),
+        Err(Ok(AuctionError::NotWhitelisted))
+    );
+
+    let mut open_params = default_params(&s);
+    open_params.bid_tick = 5;
+    let open = s.client.create_auction(&open_params);
+    assert_eq!(
+        s.client.try_validate_bid(&open, &alice, &101),
+        Err(Ok(AuctionError::BidNotOnTick))
+    );
+    assert_eq!(
+        s.client.try_validate_bid(&open, &alice, &95),
+        Err(Ok(AuctionError::BidTooLow))
+    );
+    assert_eq!(s.client.try_validate_bid(&open, &alice, &100), Ok(Ok(())));
+    assert_eq!(s.bid_token.balance(&alice), 1_000);
+
+    set_time(&s.env, 1_000);
+    assert_eq!(
+        s.client.try_validate_bid(&open, &alice, &100),
+        Err(Ok(AuctionError::AuctionEnded))
+    );
+}
+
+#[test]
+fn seller_pause_blocks_bids_until_unpaused() {
+    let s = setup();
+    let id = s.client.create_auction(&default_params(&s));
+    let alice = fund_bidder(&s, 1_000);
+    set_time(&s.env, 10);
+    s.client.seller_pause(&id);
+    assert_eq!(
+        s.client.try_place_bid(&id, &alice, &100, &None),
+        Err(Ok(AuctionError::SellerPaused))
+    );
+    // The clock keeps running while seller-paused.
+    let end = s.client.get_end_time(&id);
+    set_time(&s.env, end - 10);
+    s.client.seller_unpause(&id);
+    s.client.place_bid(&id, &alice, &100, &None);
+    assert_eq!(s.client.get_end_time(&id), end);
+}
+
+#[test]
+fn deferred_refunds_never_block_bids_and_flush_later() {
+    let s = setup();
+    let mock = s.env.register(mock_token::MockToken, ());
+    let mock_client = mock_token::MockTokenClient::new(&s.env, &mock);
+    let mut params = default_params(&s);
+    params.bid_token = mock.clone();
+    params.refund_mode = RefundMode::Deferred;
+    let id = s.client.create_auction(&params);
+    let alice = Address::generate(&s.env);
+    let bob = Address::generate(&s.env);
+    let carol = Address::generate(&s.env);
+    for who in [&alice, &bob, &carol] {
+        mock_client.mint(who, &1_000);
+    }
+    s.client.place_bid(&id, &alice, &100, &None);
+    // Alice's account starts rejecting transfers; bob's bid still lands and
+    // her refund parks.
+    mock_client.set_blocked(&alice, &true);
+    s.client.place_bid(&id, &bob, &150, &None);
+    assert_eq!(s.client.get_pending_refund(&alice), 100);
+    // Once she can receive again, the next successful bid pushes it out.
+    mock_client.set_blocked(&alice, &false);
+    s.client.place_bid(&id, &carol, &200, &None);
+    assert_eq!(s.client.get_pending_refund(&alice), 0);
+    assert_eq!(mock_client.balance(&alice), 1_000);
+    assert!(s.client.was_refunded(&alice));
+}
+
+#[test]
+fn lazy_mint_settlement_mints_to_the_winner_only_on_sale() {
+    let s = setup();
+    let nft = s.env.register(mock_nft::MockNft, ());
+    let nft_client = mock_nft::MockNftClient::new(&s.env, &nft);
+    let mut params = default_params(&s);
+    params.asset = nft.clone();
+    params.asset_amount = 7; // token id
+    params.mint_on_settle = true;
+
+    // A no-sale run mints nothing.
+    let id = s.client.create_auction(&params);
+    set_time(&s.env, s.client.get_end_time(&id));
+    s.client.settle(&id);
+    assert_eq!(nft_client.owner_of(&7), None);
+
+    // A sale mints token 7 straight to the winner.
+    let id = s.client.create_auction(&params);
+    let alice = fund_bidder(&s, 1_000);
+    s.client.place_bid(&id, &alice, &100, &None);
+    set_time(&s.env, s.client.get_end_time(&id));
+    s.client.settle(&id);
+    assert_eq!(nft_client.owner_of(&7), Some(alice.clone()));
+    assert_eq!(s.bid_token.balance(&s.seller), 98);
+}
+
+#[test]
+fn settle_shortfall_scales_the_payout_to_what_is_held() {
+    let s = setup();
+    let mock = s.env.register(mock_token::MockToken, ());
+    let mock_client = mock_token::MockTokenClient::new(&s.env, &mock);
+    let mut params = default_params(&s);
+    params.bid_token = mock.clone();
+    let id = s.client.create_auction(&params);
+    let alice = Address::generate(&s.env);
+    mock_client.mint(&alice, &500);
+    s.client.place_bid(&id, &alice, &100, &None);
+    // Part of the escrow vanishes before settlement.
+    mock_client.clawback(&s.contract_id, &40);
+
+    set_time(&s.env, s.client.get_end_time(&id));
+    s.client.settle(&id);
+    // The splits come out of the 60 actually held: 1 fee, 59 to the seller.
+    assert_eq!(s.asset.balance(&alice), 1);
+    assert_eq!(mock_client.balance(&s.fee_collector), 1);
+    assert_eq!(mock_client.balance(&s.seller), 59);
+    assert_eq!(mock_client.balance(&s.contract_id), 0);
+}
+
+#[test]
+fn settle_shortfall_is_borne_by_whoever_settles_later() {
+    let s = setup();
+    let mock = s.env.register(mock_token::MockToken, ());
+    let mock_client = mock_token::MockTokenClient::new(&s.env, &mock);
+    let mut params = default_params(&s);
+    params.bid_token = mock.clone();
+    let first = s.client.create_auction(&params);
+    let second = s.client.create_auction(&params);
+    let alice = Address::generate(&s.env);
+    let bob = Address::generate(&s.env);
+    mock_client.mint(&alice, &100);
+    mock_client.mint(&bob, &100);
+    s.client.place_bid(&first, &alice, &100, &None);
+    s.client.place_bid(&second, &bob, &100, &None);
+    // 50 of the 200 pooled escrow vanishes; nothing says whose it was.
+    mock_client.clawback(&s.contract_id, &50);
+
+    set_time(&s.env, s.client.get_end_time(&first));
+    // The cap is the pooled balance, not this auction's own escrow: the
+    // first settlement is paid in full out of what remains...
+    s.client.settle(&first);
+    assert_eq!(mock_client.balance(&s.seller), 98);
+    assert_eq!(mock_client.balance(&s.fee_collector), 2);
+    // ...and the later one absorbs the entire shortfall.
+    s.client.settle(&second);
+    assert_eq!(mock_client.balance(&s.seller), 98 + 49);
+    assert_eq!(mock_client.balance(&s.fee_collector), 2 + 1);
+    assert_eq!(mock_client.balance(&s.contract_id), 0);
+}
+
+#[test]
+fn time_since_last_bid_tracks_the_clock() {
+    let s = setup();
+    let id = s.client.create_auction(&default_params(&s));
+    assert_eq!(s.client.time_since_last_bid(&id), None);
+    let alice = fund_bidder(&s, 1_000);
+    set_time(&s.env, 100);
+    s.client.place_bid(&id, &alice, &100, &None);
+    assert_eq!(s.client.time_since_last_bid(&id), Some(0));
+    set_time(&s.env, 250);
+    assert_eq!(s.client.time_since_last_bid(&id), Some(150));
+}
+
+#[test]
+fn refund_all_drains_in_bounded_batches() {
+    let s = setup();
+    let mut params = default_params(&s);
+    params.refund_mode = RefundMode::Pull;
+    let id = s.client.create_auction(&params);
+    let alice = fund_bidder(&s, 2_000);
+    let bob = fund_bidder(&s, 2_000);
+    let carol = fund_bidder(&s, 2_000);
+    s.client.place_bid(&id, &alice, &100, &None);
+    s.client.place_bid(&id, &bob, &110, &None);
+    s.client.place_bid(&id, &carol, &120, &None);
+    // Alice and bob are outbid and pending; batches of one take two calls.
+    assert!(s.client.refund_all(&1));
+    assert!(!s.client.refund_all(&1));
+    assert!(!s.client.refund_all(&1));
+    assert_eq!(s.bid_token.balance(&alice), 2_000);
+    assert_eq!(s.bid_token.balance(&bob), 2_000);
+}
+
+#[test]
+fn refund_all_skips_undeliverable_recipients() {
+    let s = setup();
+    let mock = s.env.register(mock_token::MockToken, ());
+    let mock_client = mock_token::MockTokenClient::new(&s.env, &mock);
+    let mut params = default_params(&s);
+    params.bid_token = mock.clone();
+    let id = s.client.create_auction(&params);
+    let alice = Address::generate(&s.env);
+    let bob = Address::generate(&s.env);
+    let carol = Address::generate(&s.env);
+    mock_client.mint(&alice, &500);
+    mock_client.mint(&bob, &500);
+    mock_client.mint(&carol, &500);
+
+    // Immediate-mode refunds that bounce are parked, so the queue head is
+    // by construction an address that could not receive.
+    s.client.place_bid(&id, &alice, &100, &None);
+    mock_client.set_blocked(&alice, &true);
+    s.client.place_bid(&id, &bob, &200, &None);
+    mock_client.set_blocked(&bob, &true);
+    s.client.place_bid(&id, &carol, &300, &None);
+    assert!(s.client.has_pending(&alice));
+    assert!(s.client.has_pending(&bob));
+
+    // Alice still can't receive; she must not trap Bob behind her.
+    mock_client.set_blocked(&bob, &false);
+    assert!(s.client.refund_all(&10));
+    assert_eq!(mock_client.balance(&bob), 500);
+    assert!(s.client.has_pending(&alice));
+
+    mock_client.set_blocked(&alice, &false);
+    assert!(!s.client.refund_all(&10));
+    assert_eq!(mock_client.balance(&alice), 500);
+}
+
+#[test]
+fn supported_kinds_lists_every_format() {
+    let s = setup();
+    let kinds = s.client.supported_kinds();
+    assert_eq!(kinds.len(), 6);
+    // Every kind reported here must also appear in the feature list.
+    let features = s.client.features();
+    for kind in [symbol_short!("english"), symbol_short!("dutch"), symbol_short!("vickrey")] {
+        assert!(kinds.contains(&kind));
+        assert!(features.contains(&kind));
+    }
+    assert!(kinds.contains(&symbol_short!("reverse")));
+}
+
+#[test]
+fn extension_cannot_produce_an_end_in_the_past() {
+    let s = setup();
+    let id = s.client.create_auction(&default_params(&s));
+    // The window lapses unsettled; a short extension would still end in the
+    // past and is rejected, while a long one re-opens bidding.
+    set_time(&s.env, 2_000);
+    assert_eq!(
+        s.client.try_extend_auction(&id, &100),
+        Err(Ok(AuctionError::EndInPast))
+    );
+    s.client.extend_auction(&id, &1_500);
+    assert_eq!(s.client.get_end_time(&id), 2_500);
+}
+
+#[test]
+fn donation_share_pays_the_charity_and_reduces_the_seller() {
+    let s = setup();
+    let charity = Address::generate(&s.env);
+    let mut params = default_params(&s);
+    params.donation = RevenueShare {
+        recipient: Some(charity.clone()),
+        bps: 1_000, // 10%
+    };
+    let id = s.client.create_auction(&params);
+    let alice = fund_bidder(&s, 1_000);
+    s.client.place_bid(&id, &alice, &200, &None);
+    set_time(&s.env, s.client.get_end_time(&id));
+    s.client.settle(&id);
+    assert_eq!(
+        event_count(&s, (symbol_short!("donation"), id).into_val(&s.env)),
+        1
+    );
+    assert_eq!(s.bid_token.balance(&charity), 20);
+    // 200 hammer - 4 fee - 20 donation.
+    assert_eq!(s.bid_token.balance(&s.seller), 176);
+}
+
+#[test]
+fn retract_bid_charges_penalty_and_reinstates_runner_up() {
+    let s = setup();
+    let mut params = default_params(&s);
+    params.refund_mode = RefundMode::Pull;
+    params.retraction_penalty = 20;
+    let id = s.client.create_auction(&params);
+    let alice = fund_bidder(&s, 1_000);
+    let bob = fund_bidder(&s, 1_000);
+    s.client.place_bid(&id, &alice, &100, &None);
+    s.client.place_bid(&id, &bob, &200, &None);
+    // Only the sitting leader may retract.
+    assert_eq!(
+        s.client.try_retract_bid(&id, &alice),
+        Err(Ok(AuctionError::NotWinner))
+    );
+    s.client.retract_bid(&id, &bob);
+    assert_eq!(s.bid_token.balance(&bob), 980);
+    assert_eq!(s.bid_token.balance(&s.seller), 20);
+    // Alice's parked refund turns back into live escrow behind her lead.
+    let view = s.client.get_highest_bid(&id);
+    assert_eq!(view.bidder, Some(alice.clone()));
+    assert_eq!(view.amount, Some(100));
+    assert!(!s.client.has_pending(&alice));
+    assert_escrow_invariants(&s, &[id]);
+
+    set_time(&s.env, s.client.get_end_time(&id));
+    s.client.settle(&id);
+    assert_eq!(s.asset.balance(&alice), 1);
+}
+
+#[test]
+fn buy_now_overpayment_stays_with_the_buyer() {
+    let s = setup();
+    let mut params = default_params(&s);
+    params.buy_now_price = 300;
+    let id = s.client.create_auction(&params);
+    let alice = fund_bidder(&s, 1_000);
+    s.client.buy_now(&id, &alice, &450);
+    // Exactly the listed price is charged; the 150 surplus never moves.
+    assert_eq!(s.bid_token.balance(&alice), 700);
+    assert_eq!(s.bid_token.balance(&s.seller), 294);
+    assert_eq!(s.client.get_winner_total_cost(&id), 300);
+}
+
+#[test]
+fn buy_now_charges_and_pays_the_buyer_premium() {
+    let s = setup();
+    let mut params = default_params(&s);
+    params.buy_now_price = 400;
+    params.buyer_premium_bps = 1_000;
+    let id = s.client.create_auction(&params);
+    let alice = fund_bidder(&s, 1_000);
+    s.client.buy_now(&id, &alice, &400);
+    // 400 hammer + 40 premium leave the buyer; the collector receives the
+    // 2% fee plus the premium, and the total-cost query matches.
+    assert_eq!(s.bid_token.balance(&alice), 560);
+    assert_eq!(s.bid_token.balance(&s.seller), 392);
+    assert_eq!(s.bid_token.balance(&s.fee_collector), 8 + 40);
+    assert_eq!(s.bid_token.balance(&s.contract_id), 0);
+    assert_eq!(s.client.get_winner_total_cost(&id), 440);
+}
+
+#[test]
+fn list_ending_before_returns_only_the_window() {
+    let s = setup();
+    let mut params = default_params(&s);
+    params.duration = 500;
+    let short = s.client.create_auction(&params);
+    params.duration = 1_000;
+    let medium = s.client.create_auction(&params);
+    params.duration = 5_000;
+    s.client.create_auction(&params);
+
+    assert_eq!(s.client.list_ending_before(&1_200), vec![&s.env, short, medium]);
+    // Settled auctions drop out of the shelf.
+    set_time(&s.env, 500);
+    s.client.settle(&short);
+    assert_eq!(s.client.list_ending_before(&1_200), vec![&s.env, medium]);
+}
+
+#[test]
+fn anti_snipe_can_ignore_sub_reserve_bids() {
+    let s = setup();
+    let mut params = default_params(&s);
+    params.soft_reserve = 500;
+    params.anti_snipe.window = 100;
+    params.anti_snipe.period = 200;
+    params.anti_snipe.above_reserve_only = true;
+    let id = s.client.create_auction(&params);
+    let alice = fund_bidder(&s, 2_000);
+    let bob = fund_bidder(&s, 2_000);
+    let end = s.client.get_end_time(&id);
+    set_time(&s.env, end - 50);
+    // A late bid below the soft reserve leaves the schedule alone.
+    s.client.place_bid(&id, &alice, &100, &None);
+    assert_eq!(s.client.get_end_time(&id), end);
+    // A late bid clearing it extends as usual.
+    s.client.place_bid(&id, &bob, &500, &None);
+    assert_eq!(s.client.get_end_time(&id), end + 200);
+}
+
+#[test]
+fn winner_total_cost_sums_hammer_premium_and_deposit() {
+    let s = setup();
+    let mut params = default_params(&s);
+    params.deposit = 25;
+    params.buyer_premium_bps = 1_000; // 10%
+    let id = s.client.create_auction(&params);
+    let alice = fund_bidder(&s, 1_000);
+    s.client.place_bid(&id, &alice, &200, &None);
+    assert_eq!(
+        s.client.try_get_winner_total_cost(&id),
+        Err(Ok(AuctionError::AuctionNotEnded))
+    );
+    set_time(&s.env, s.client.get_end_time(&id));
+    s.client.settle(&id);
+    // 200 hammer + 20 premium + 25 forfeited deposit.
+    assert_eq!(s.client.get_winner_total_cost(&id), 245);
+    assert_eq!(s.bid_token.balance(&alice), 1_000 - 245);
+}
+
+#[test]
+fn whole_units_only_rejects_fractional_bids() {
+    let s = setup();
+    let mut params = default_params(&s);
+    params.whole_units_only = true;
+    let id = s.client.create_auction(&params);
+    // The Stellar asset contract reports 7 decimals.
+    let alice = fund_bidder(&s, 30_000_000);
+    assert_eq!(
+        s.client.try_place_bid(&id, &alice, &10_000_001, &None),
+        Err(Ok(AuctionError::BidNotOnTick))
+    );
+    s.client.place_bid(&id, &alice, &10_000_000, &None);
+}
+
+#[test]
+fn increase_bid_reuses_escrow_and_pending_refunds() {
+    let s = setup();
+    let mut params = default_params(&s);
+    params.refund_mode = RefundMode::Pull;
+    let id = s.client.create_auction(&params);
+    let alice = fund_bidder(&s, 1_000);
+    let bob = fund_bidder(&s, 1_000);
+
+    s.client.place_bid(&id, &alice, &100, &None);
+    // Leader tops up by the delta only.
+    s.client.increase_bid(&id, &alice, &50);
+    assert_eq!(s.bid_token.balance(&alice), 850);
+    assert_eq!(s.client.get_highest_bid(&id).amount, Some(150));
+    assert_eq!(s.bid_token.balance(&s.contract_id), 150);
+
+    s.client.place_bid(&id, &bob, &200, &None);
+    // Alice's 150 sits pending; recycling it means only 70 leaves her wallet
+    // to reach a 220 total.
+    s.client.increase_bid(&id, &alice, &70);
+    assert_eq!(s.bid_token.balance(&alice), 780);
+    assert_eq!(s.client.get_highest_bid(&id).bidder, Some(alice.clone()));
+    assert_eq!(s.client.get_highest_bid(&id).amount, Some(220));
+    assert!(!s.client.has_pending(&alice));
+    assert_escrow_invariants(&s, &[id]);
+}
+
+#[test]
+fn increase_bid_does_not_inherit_the_leaders_refund_to() {
+    let s = setup();
+    let id = s.client.create_auction(&default_params(&s));
+    let alice = fund_bidder(&s, 1_000);
+    let bob = fund_bidder(&s, 1_000);
+    let carol = fund_bidder(&s, 1_000);
+    let custody = Address::generate(&s.env);
+
+    s.client.place_bid(&id, &alice, &100, &None);
+    s.client.place_bid(&id, &bob, &200, &Some(custody.clone()));
+    // Alice retakes the lead via `increase_bid` while Bob's custody address
+    // is the stored `refund_to`; it must not attach to her bid. Bob's own
+    // refund still honors it.
+    s.client.increase_bid(&id, &alice, &250);
+    assert_eq!(s.bid_token.balance(&custody), 200);
+    s.client.place_bid(&id, &carol, &300, &None);
+    assert_eq!(s.bid_token.balance(&alice), 1_000);
+    assert_eq!(s.bid_token.balance(&custody), 200);
+    assert_escrow_invariants(&s, &[id]);
+}
+
+#[test]
+fn has_won_marks_only_the_winner() {
+    let s = setup();
+    let id = s.client.create_auction(&default_params(&s));
+    let alice = fund_bidder(&s, 1_000);
+    let bob = fund_bidder(&s, 1_000);
+    s.client.place_bid(&id, &alice, &100, &None);
+    s.client.place_bid(&id, &bob, &200, &None);
+    set_time(&s.env, s.client.get_end_time(&id));
+    s.client.settle(&id);
+    assert!(s.client.has_won(&bob));
+    assert!(!s.client.has_won(&alice));
+}
+
+#[test]
+fn highest_deposit_tie_break_resolves_equal_bids() {
+    let s = setup();
+    let mut params = default_params(&s);
+    params.deposit = 10;
+    params.tie_break = TieBreak::HighestDeposit;
+    let id = s.client.create_auction(&params);
+    let alice = fund_bidder(&s, 1_000);
+    let bob = fund_bidder(&s, 1_000);
+    s.client.place_bid(&id, &alice, &100, &None);
+    // An equal bid backed by the same stake still loses to the leader.
+    assert_eq!(
+        s.client.try_place_bid(&id, &bob, &100, &None),
+        Err(Ok(AuctionError::BidTooLow))
+    );
+    // A larger deposit breaks the tie in the challenger's favor.
+    s.client.top_up_deposit(&id, &bob, &15);
+    s.client.place_bid(&id, &bob, &100, &None);
+    assert_eq!(s.client.get_highest_bid(&id).bidder, Some(bob.clone()));
+    // Now alice is the smaller-staked side and her equal bid is rejected.
+    assert_eq!(
+        s.client.try_place_bid(&id, &alice, &100, &None),
+        Err(Ok(AuctionError::BidTooLow))
+    );
+}
+
+#[test]
+fn external_escrow_address_holds_and_releases_the_asset() {
+    let s = setup();
+    let escrow = s.env.register(mock_treasury::MockTreasury, ());
+    let mut params = default_params(&s);
+    params.escrow_address = Some(escrow.clone());
+    let id = s.client.create_auction(&params);
+    // The lot parks with the custodian, not the auction contract.
+    assert_eq!(s.asset.balance(&escrow), 1);
+    assert_eq!(s.asset.balance(&s.contract_id), 0);
+    // The custodian authorizes the contract to pull the lot back at settle.
+    s.asset
+        .approve(&escrow, &s.contract_id, &1, &(s.env.ledger().sequence() + 100));
+
+    let alice = fund_bidder(&s, 1_000);
+    s.client.place_bid(&id, &alice, &100, &None);
+    set_time(&s.env, s.client.get_end_time(&id));
+    s.client.settle(&id);
+    assert_eq!(s.asset.balance(&escrow), 0);
+    assert_eq!(s.asset.balance(&alice), 1);
+}
+
+#[test]
+fn contract_address_works_as_seller_end_to_end() {
+    let s = setup();
+    let treasury = s.env.register(mock_treasury::MockTreasury, ());
+    let asset_admin = token::StellarAssetClient::new(&s.env, &s.asset.address);
+    asset_admin.mint(&treasury, &1);
+    let mut params = default_params(&s);
+    params.seller = treasury.clone();
+    let id = s.client.create_auction(&params);
+    assert_eq!(s.asset.balance(&treasury), 0);
+
+    let alice = fund_bidder(&s, 1_000);
+    s.client.place_bid(&id, &alice, &100, &None);
+    set_time(&s.env, s.client.get_end_time(&id));
+    s.client.settle(&id);
+    // Hammer price minus the 2% fee lands on the contract seller's balance,
+    // and the asset reaches the winner as usual.
+    assert_eq!(s.bid_token.balance(&treasury), 98);
+    assert_eq!(s.asset.balance(&alice), 1);
+}
+
+#[test]
+fn is_settled_flips_after_settle() {
+    let s = setup();
+    let id = s.client.create_auction(&default_params(&s));
+    assert!(!s.client.is_settled(&id));
+    set_time(&s.env, s.client.get_end_time(&id));
+    s.client.settle(&id);
+    assert!(s.client.is_settled(&id));
+}
+
+#[test]
+fn minimum_escalation_raises_the_bar_per_distinct_bidder() {
+    let s = setup();
+    let mut params = default_params(&s);
+    params.minimum_escalation = 5;
+    let id = s.client.create_auction(&params);
+    let alice = fund_bidder(&s, 1_000);
+    let bob = fund_bidder(&s, 1_000);
+    let carol = fund_bidder(&s, 1_000);
+    s.client.place_bid(&id, &alice, &100, &None);
+    // One bidder so far: only the flat increment applies.
+    s.client.place_bid(&id, &bob, &110, &None);
+    // Two distinct bidders: the third must clear increment + escalation.
+    assert_eq!(
+        s.client.try_place_bid(&id, &carol, &124, &None),
+        Err(Ok(AuctionError::BidTooLow))
+    );
+    s.client.place_bid(&id, &carol, &125, &None);
+    assert_eq!(
+        s.client.try_place_bid(&id, &alice, &144, &None),
+        Err(Ok(AuctionError::BidTooLow))
+    );
+}
+
+#[test]
+fn next_transition_walks_the_lifecycle() {
+    let s = setup();
+    let mut params = default_params(&s);
+    params.start_time = 50;
+    params.timing.settle_delay = 20;
+    let id = s.client.create_auction(&params);
+    assert_eq!(
+        s.client.next_transition(&id),
+        (symbol_short!("start"), 50)
+    );
+    set_time(&s.env, 50);
+    let end = s.client.get_end_time(&id);
+    assert_eq!(s.client.next_transition(&id), (symbol_short!("end"), end));
+    set_time(&s.env, end);
+    assert_eq!(
+        s.client.next_transition(&id),
+        (symbol_short!("settle"), end + 20)
+    );
+    set_time(&s.env, end + 20);
+    s.client.settle(&id);
+    assert_eq!(s.client.next_transition(&id), (symbol_short!("done"), 0));
+}
+
+#[test]
+fn freeze_clock_on_pause_extends_end_by_paused_interval() {
+    let s = setup();
+    let mut params = default_params(&s);
+    params.freeze_clock_on_pause = true;
+    let id = s.client.create_auction(&params);
+    let end = s.client.get_end_time(&id);
+    set_time(&s.env, 100);
+    s.client.seller_pause(&id);
+    set_time(&s.env, 350);
+    s.client.seller_unpause(&id);
+    assert_eq!(s.client.get_end_time(&id), end + 250);
+}
+
+#[test]
+fn max_escrow_caps_the_held_bid_token() {
+    let s = setup();
+    let mut params = default_params(&s);
+    params.max_escrow = Some(150);
+    let id = s.client.create_auction(&params);
+    let alice = fund_bidder(&s, 1_000);
+    s.client.place_bid(&id, &alice, &120, &None);
+    assert_eq!(
+        s.client.try_place_bid(&id, &alice, &200, &None),
+        Err(Ok(AuctionError::EscrowCapExceeded))
+    );
+    assert_eq!(
+        s.client.try_validate_bid(&id, &alice, &200),
+        Err(Ok(AuctionError::EscrowCapExceeded))
+    );
+    // A raise that stays under the cap is still accepted.
+    s.client.place_bid(&id, &alice, &140, &None);
+}
+
+#[test]
+fn was_refunded_tracks_immediate_and_pull_refunds() {
+    let s = setup();
+    let alice = fund_bidder(&s, 1_000);
+    let bob = fund_bidder(&s, 1_000);
+
+    let id = s.client.create_auction(&default_params(&s));
+    s.client.place_bid(&id, &alice, &100, &None);
+    assert!(!s.client.was_refunded(&alice));
+    s.client.place_bid(&id, &bob, &200, &None);
+    assert!(s.client.was_refunded(&alice));
+
+    let mut params = default_params(&s);
+    params.refund_mode = RefundMode::Pull;
+    let id = s.client.create_auction(&params);
+    s.client.place_bid(&id, &bob, &100, &None);
+    s.client.place_bid(&id, &alice, &200, &None);
+    // Outbid but not yet withdrawn: the credit is pending, not delivered.
+    assert!(!s.client.was_refunded(&bob));
+    s.client.withdraw(&bob);
+    assert!(s.client.was_refunded(&bob));
+}
+
+#[test]
+fn fee_schedule_applies_the_tier_the_price_lands_in() {
+    let s = setup();
+    // 5% under 500, 3% from 500, 1% from 5_000; rejects unsorted input.
+    assert_eq!(
+        s.client.try_set_fee_schedule(&vec![&s.env, (500, 300), (0, 500)]),
+        Err(Ok(AuctionError::InvalidParams))
+    );
+    s.client
+        .set_fee_schedule(&vec![&s.env, (0, 500), (500, 300), (5_000, 100)]);
+
+    let alice = fund_bidder(&s, 20_000);
+    for (bid, fee) in [(200i128, 10i128), (600, 18), (6_000, 60)] {
+        let id = s.client.create_auction(&default_params(&s));
+        let collector_before = s.bid_token.balance(&s.fee_collector);
+        let seller_before = s.bid_token.balance(&s.seller);
+        s.client.place_bid(&id, &alice, &bid, &None);
+        set_time(&s.env, s.client.get_end_time(&id));
+        s.client.settle(&id);
+        assert_eq!(s.bid_token.balance(&s.fee_collector) - collector_before, fee);
+        assert_eq!(s.bid_token.balance(&s.seller) - seller_before, bid - fee);
+    }
+}
+
+#[test]
+fn convert_to_fixed_price_requires_zero_bids() {
+    let s = setup();
+    let id = s.client.create_auction(&default_params(&s));
+    let alice = fund_bidder(&s, 1_000);
+
+    s.client.convert_to_fixed_price(&id, &400);
+    // Bidding under the listing price is shut off; buy-now works at it.
+    assert_eq!(
+        s.client.try_place_bid(&id, &alice, &300, &None),
+        Err(Ok(AuctionError::BidTooLow))
+    );
+    s.client.buy_now(&id, &alice, &400);
+    assert_eq!(s.asset.balance(&alice), 1);
+
+    // A second auction with a live bid refuses conversion.
+    let other = s.client.create_auction(&default_params(&s));
+    let bob = fund_bidder(&s, 1_000);
+    s.client.place_bid(&other, &bob, &100, &None);
+    assert_eq!(
+        s.client.try_convert_to_fixed_price(&other, &400),
+        Err(Ok(AuctionError::AlreadyStarted))
+    );
+}
+
+#[test]
+fn anti_snipe_boundary_and_stacked_extensions() {
+    let s = setup();
+    let mut params = default_params(&s);
+    params.anti_snipe.window = 100;
+    params.anti_snipe.period = 200;
+    let id = s.client.create_auction(&params);
+    let alice = fund_bidder(&s, 5_000);
+    let bob = fund_bidder(&s, 5_000);
+
+    // One second outside the window: no extension.
+    bid_at_offset_and_assert_end(&s, id, &alice, 100, 101, 1_000);
+    // Exactly on the window boundary extends, and each following late bid
+    // stacks another period onto the already-extended end.
+    bid_at_offset_and_assert_end(&s, id, &bob, 120, 100, 1_200);
+    bid_at_offset_and_assert_end(&s, id, &alice, 140, 50, 1_400);
+    bid_at_offset_and_assert_end(&s, id, &bob, 160, 1, 1_600);
+}
+
+#[test]
+fn cleanup_drops_heavy_state_but_keeps_the_outcome() {
+    let s = setup();
+    let id = s.client.create_auction(&default_params(&s));
+    let alice = fund_bidder(&s, 500);
+    s.client.place_bid(&id, &alice, &100, &None);
+
+    assert_eq!(
+        s.client.try_cleanup(&id),
+        Err(Ok(AuctionError::AuctionNotEnded))
+    );
+    set_time(&s.env, 1_000);
+    s.client.settle(&id);
+    s.client.cleanup(&id);
+
+    assert_eq!(
+        s.client.try_get_auction(&id),
+        Err(Ok(AuctionError::AuctionNotFound))
+    );
+    s.env.as_contract(&s.contract_id, || {
+        assert!(!s.env.storage().persistent().has(&DataKey::Auction(id)));
+        assert!(!s.env.storage().persistent().has(&DataKey::IsSettle(id)));
+        assert!(!s.env.storage().persistent().has(&DataKey::Bidders(id)));
+        assert!(!s
+            .env
+            .storage()
+            .persistent()
+            .has(&DataKey::ForfeitedDeposit(id)));
+    });
+    let outcomes = s.client.list_outcomes(&0, &10);
+    assert_eq!(outcomes.len(), 1);
+    assert_eq!(outcomes.get_unchecked(0).winner, Some(alice));
+}
+
+#[test]
+fn cleanup_refuses_while_a_vickrey_claim_is_open() {
+    let s = setup();
+    let mut params = default_params(&s);
+    params.kind = AuctionKind::Vickrey;
+    let id = s.client.create_auction(&params);
+    let alice = fund_bidder(&s, 500);
+    s.client.place_bid(&id, &alice, &100, &None);
+
+    set_time(&s.env, 1_000);
+    s.client.settle(&id);
+    assert_eq!(
+        s.client.try_cleanup(&id),
+        Err(Ok(AuctionError::ClaimsOutstanding))
+    );
+    s.client.claim(&id);
+    s.client.cleanup(&id);
+}
+
+#[test]
+fn list_outcomes_paginates_settled_auctions_in_id_order() {
+    let s = setup();
+    let alice = fund_bidder(&s, 10_000);
+    // Three auctions; the middle one stays live and must not appear.
+    let sold = s.client.create_auction(&default_params(&s));
+    let live = s.client.create_auction(&default_params(&s));
+    let unsold = s.client.create_auction(&default_params(&s));
+    s.client.place_bid(&sold, &alice, &150, &None);
+
+    set_time(&s.env, 1_000);
+    s.client.settle(&sold);
+    s.client.settle(&unsold);
+    s.client.extend_auction(&live, &5_000);
+
+    let page = s.client.list_outcomes(&0, &10);
+    assert_eq!(page.len(), 2);
+    let first = page.get_unchecked(0);
+    assert_eq!(first.id, sold);
+    assert_eq!(first.winner, Some(alice));
+    assert_eq!(first.final_price, 150);
+    let second = page.get_unchecked(1);
+    assert_eq!(second.id, unsold);
+    assert_eq!(second.winner, None);
+    assert_eq!(second.final_price, 0);
+
+    // Offset walks past the first outcome; a stale offset yields nothing.
+    assert_eq!(s.client.list_outcomes(&1, &10).len(), 1);
+    assert_eq!(s.client.list_outcomes(&2, &10).len(), 0);
+}
+
+#[test]
+fn migrate_rewrites_legacy_state_under_id_zero() {
+    let s = setup();
+    // Seed the pre-refactor layout directly: one un-keyed live auction.
+    let alice = fund_bidder(&s, 500);
+    let params = default_params(&s);
+    let legacy = Auction {
+        params: params.clone(),
+        start_time: 0,
+        end_time: 1_000,
+        highest_bidder: Some(alice.clone()),
+        highest_bid: 150,
+        refund_to: None,
+        second_bidder: None,
+        second_bid: 0,
+        last_bid_time: 10,
+        settled_at: 0,
+        remaining: 1,
+        proceeds: 0,
+        relists_done: 0,
+        seller_paused_at: 0,
+        distinct_bidders: 0,
+    };
+    s.env.as_contract(&s.contract_id, || {
+        s.env.storage().persistent().set(&LegacyKey::Auction, &legacy);
+    });
+    assert_eq!(
+        s.client.try_get_auction(&0),
+        Err(Ok(AuctionError::AuctionNotFound))
+    );
+
+    s.client.migrate();
+    let migrated = s.client.get_auction(&0);
+    assert_eq!(migrated.highest_bidder, Some(alice));
+    assert_eq!(migrated.highest_bid, 150);
+    assert_eq!(s.client.get_auction_count(), 1);
+    assert_eq!(s.client.list_by_seller(&s.seller, &0, &10), vec![&s.env, 0]);
+
+    // One-shot: a second call is rejected.
+    assert_eq!(
+        s.client.try_migrate(),
+        Err(Ok(AuctionError::AlreadyInitialized))
+    );
+}
+
+#[test]
+fn buyer_premium_charges_winner_and_leaves_hammer_price_intact() {
+    let s = setup();
+    let mut params = default_params(&s);
+    // 10% premium on top of the 2% seller-side fee.
+    params.buyer_premium_bps = 1_000;
+    let id = s.client.create_auction(&params);
+    let alice = fund_bidder(&s, 1_000);
+    let bob = fund_bidder(&s, 1_000);
+
+    s.client.place_bid(&id, &alice, &100, &None);
+    assert_eq!(s.bid_token.balance(&alice), 890);
+    s.client.place_bid(&id, &bob, &200, &None);
+    // Alice's refund includes her escrowed premium.
+    assert_eq!(s.bid_token.balance(&alice), 1_000);
+    assert_eq!(s.bid_token.balance(&bob), 780);
+
+    set_time(&s.env, 1_000);
+    s.client.settle(&id);
+    // Seller is paid off the hammer price alone; the collector receives the
+    // 2% fee plus the whole premium.
+    assert_eq!(s.bid_token.balance(&s.seller), 196);
+    assert_eq!(s.bid_token.balance(&s.fee_collector), 4 + 20);
+    assert_eq!(s.bid_token.balance(&s.contract_id), 0);
+}
+
+#[test]
+fn time_until_start_counts_down_to_zero() {
+    let s = setup();
+    let mut params = default_params(&s);
+    params.start_time = 5_000;
+    let id = s.client.create_auction(&params);
+
+    assert_eq!(s.client.get_time_until_start(&id), 5_000);
+    set_time(&s.env, 3_000);
+    assert_eq!(s.client.get_time_until_start(&id), 2_000);
+    set_time(&s.env, 5_000);
+    assert_eq!(s.client.get_time_until_start(&id), 0);
+    set_time(&s.env, 9_000);
+    assert_eq!(s.client.get_time_until_start(&id), 0);
+}
+
+#[test]
+fn split_top_two_divides_lot_and_charges_both_bids() {
+    let s = setup();
+    let mut params = default_params(&s);
+    params.kind = AuctionKind::SplitTopTwo;
+    params.asset_amount = 10;
+    let id = s.client.create_auction(&params);
+    let alice = fund_bidder(&s, 1_000);
+    let bob = fund_bidder(&s, 1_000);
+    let carol = fund_bidder(&s, 1_000);
+
+    s.client.place_bid(&id, &alice, &100, &None);
+    s.client.place_bid(&id, &bob, &200, &None);
+    // Bob leads and Alice's escrow stays: she's the funded runner-up.
+    assert_eq!(s.bid_token.balance(&alice), 900);
+    s.client.place_bid(&id, &carol, &300, &None);
+    // Carol displaces Bob into second; Alice drops out and is refunded.
+    assert_eq!(s.bid_token.balance(&alice), 1_000);
+    assert_eq!(s.bid_token.balance(&s.contract_id), 500);
+
+    set_time(&s.env, 1_000);
+    s.client.settle(&id);
+    assert_eq!(s.asset.balance(&carol), 7);
+    assert_eq!(s.asset.balance(&bob), 3);
+    assert_eq!(s.bid_token.balance(&carol), 700);
+    assert_eq!(s.bid_token.balance(&bob), 800);
+    // Combined proceeds of 500 split as 2% fee + seller remainder, and the
+    // outcome records that combined price.
+    assert_eq!(s.bid_token.balance(&s.fee_collector), 10);
+    assert_eq!(s.bid_token.balance(&s.seller), 490);
+    assert_eq!(s.client.get_final_price(&id), 500);
+}
+
+#[test]
+fn split_top_two_pays_the_premium_on_the_combined_price() {
+    let s = setup();
+    let mut params = default_params(&s);
+    params.kind = AuctionKind::SplitTopTwo;
+    params.asset_amount = 10;
+    params.buyer_premium_bps = 1_000;
+    let id = s.client.create_auction(&params);
+    let alice = fund_bidder(&s, 1_000);
+    let bob = fund_bidder(&s, 1_000);
+
+    s.client.place_bid(&id, &alice, &100, &None);
+    s.client.place_bid(&id, &bob, &200, &None);
+    // Both live bids escrow their premium: 110 + 220.
+    assert_eq!(s.bid_token.balance(&s.contract_id), 330);
+
+    set_time(&s.env, 1_000);
+    s.client.settle(&id);
+    // Seller gets the combined 300 minus the 2% fee; the collector gets
+    // that fee plus the full 30 premium. Nothing stays stranded.
+    assert_eq!(s.bid_token.balance(&s.seller), 294);
+    assert_eq!(s.bid_token.balance(&s.fee_collector), 6 + 30);
+    assert_eq!(s.bid_token.balance(&s.contract_id), 0);
+}
+
+#[test]
+fn quiet_period_delays_settle_after_late_bids() {
+    let s = setup();
+    let mut params = default_params(&s);
+    params.timing.quiet_period = 300;
+    let id = s.client.create_auction(&params);
+    let alice = fund_bidder(&s, 500);
+    let bob = fund_bidder(&s, 500);
+
+    s.client.place_bid(&id, &alice, &100, &None);
+    // A bid right at the wire restarts the quiet clock without moving
+    // `end_time`.
+    set_time(&s.env, 999);
+    s.client.place_bid(&id, &bob, &120, &None);
+
+    set_time(&s.env, 1_000);
+    assert_eq!(
+        s.client.try_settle(&id),
+        Err(Ok(AuctionError::AuctionNotEnded))
+    );
+    set_time(&s.env, 1_298);
+    assert_eq!(
+        s.client.try_settle(&id),
+        Err(Ok(AuctionError::AuctionNotEnded))
+    );
+    set_time(&s.env, 1_299);
+    s.client.settle(&id);
+    assert_eq!(s.asset.balance(&bob), 1);
+}
+
+#[test]
+fn create_nonce_rejects_retries_but_not_fresh_nonces() {
+    let s = setup();
+    let params = default_params(&s);
+    let id = s.client.create_with_nonce(&params, &7);
+    assert_eq!(
+        s.client.try_create_with_nonce(&params, &7),
+        Err(Ok(AuctionError::NonceUsed))
+    );
+    let other = s.client.create_with_nonce(&params, &8);
+    assert_ne!(id, other);
+}
+
+#[test]
+fn kyc_gate_rejects_unverified_bidders() {
+    let s = setup();
+    let kyc = s.env.register(mock_kyc::MockKyc, ());
+    let kyc_client = mock_kyc::MockKycClient::new(&s.env, &kyc);
+    s.client.set_kyc_contract(&Some(kyc.clone()));
+
+    let id = s.client.create_auction(&default_params(&s));
+    let alice = fund_bidder(&s, 500);
+    assert_eq!(
+        s.client.try_place_bid(&id, &alice, &100, &None),
+        Err(Ok(AuctionError::NotVerified))
+    );
+
+    kyc_client.set_verified(&alice, &true);
+    s.client.place_bid(&id, &alice, &100, &None);
+
+    // Nothing is cached: revoking the attestation blocks the next bid.
+    kyc_client.set_verified(&alice, &false);
+    assert_eq!(
+        s.client.try_place_bid(&id, &alice, &120, &None),
+        Err(Ok(AuctionError::NotVerified))
+    );
+
+    // Clearing the config reopens bidding to everyone.
+    s.client.set_kyc_contract(&None);
+    s.client.place_bid(&id, &alice, &120, &None);
+}
+
+#[test]
+fn fee_on_transfer_bid_records_received_amount() {
+    let s = setup();
+    let mock = s.env.register(mock_token::MockToken, ());
+    let mock_client = mock_token::MockTokenClient::new(&s.env, &mock);
+    mock_client.set_fee(&10);
+
+    let mut params = default_params(&s);
+    params.bid_token = mock.clone();
+    let id = s.client.create_auction(&params);
+    let alice = Address::generate(&s.env);
+    mock_client.mint(&alice, &500);
+
+    // The fee eats the margin over the reserve: only 95 arrives.
+    assert_eq!(
+        s.client.try_place_bid(&id, &alice, &105, &None),
+        Err(Ok(AuctionError::BidTooLow))
+    );
+    // Sending 110 delivers 100; that received amount is the recorded bid.
+    s.client.place_bid(&id, &alice, &110, &None);
+    let auction = s.client.get_auction(&id);
+    assert_eq!(auction.highest_bid, 100);
+    assert_eq!(mock_client.balance(&s.contract_id), 100);
+}
+
+#[test]
+fn short_escrow_balance_parks_refund_instead_of_blocking_bid() {
+    let s = setup();
+    let mock = s.env.register(mock_token::MockToken, ());
+    let mock_client = mock_token::MockTokenClient::new(&s.env, &mock);
+
+    let mut params = default_params(&s);
+    params.bid_token = mock.clone();
+    params.buy_now_price = 200;
+    let id = s.client.create_auction(&params);
+    let alice = Address::generate(&s.env);
+    let bob = Address::generate(&s.env);
+    mock_client.mint(&alice, &500);
+    mock_client.mint(&bob, &500);
+
+    s.client.place_bid(&id, &alice, &100, &None);
+    // The token misbehaves: part of the escrow vanishes and the next lock
+    // credits nothing, leaving the contract short of Alice's refund.
+    mock_client.clawback(&s.contract_id, &30);
+    mock_client.set_skimmed(&s.contract_id, &true);
+
+    s.client.buy_now(&id, &bob, &200);
+    // Bob's purchase still lands; Alice's refund parks as a pending claim.
+    assert_eq!(s.asset.balance(&bob), 1);
+    assert_eq!(s.client.get_pending_refund(&alice), 100);
+}
+
+#[test]
+fn escrow_lock_and_release_roundtrip() {
+    let s = setup();
+    // `as_contract` frames are non-root invocations.
+    s.env.mock_all_auths_allowing_non_root_auth();
+    let alice = fund_bidder(&s, 100);
+    s.env.as_contract(&s.contract_id, || {
+        escrow::lock(&s.env, &s.bid_token.address, &alice, 60);
+    });
+    assert_eq!(s.bid_token.balance(&alice), 40);
+    assert_eq!(s.bid_token.balance(&s.contract_id), 60);
+    s.env.as_contract(&s.contract_id, || {
+        escrow::release(&s.env, &s.bid_token.address, &alice, 25);
+    });
+    assert_eq!(s.bid_token.balance(&alice), 65);
+    assert_eq!(s.bid_token.balance(&s.contract_id), 35);
+}
+
+#[test]
+fn escrow_try_release_surfaces_failure() {
+    let s = setup();
+    s.env.mock_all_auths_allowing_non_root_auth();
+    let alice = fund_bidder(&s, 100);
+    s.env.as_contract(&s.contract_id, || {
+        escrow::lock(&s.env, &s.bid_token.address, &alice, 50);
+        // More than the contract holds comes back as Err, not a trap.
+        assert_eq!(
+            escrow::try_release(&s.env, &s.bid_token.address, &alice, 80),
+            Err(())
+        );
+        assert_eq!(
+            escrow::try_release(&s.env, &s.bid_token.address, &alice, 50),
+            Ok(())
+        );
+    });
+    assert_eq!(s.bid_token.balance(&alice), 100);
+}
+
+#[test]
+fn quote_in_converts_via_oracle() {
+    let s = setup();
+    let oracle = s.env.register(mock_oracle::MockOracle, ());
+    assert_eq!(s.client.quote_in(&oracle, &100), 250);
+    assert_eq!(s.client.quote_in(&oracle, &0), 0);
+}
+
+#[test]
+fn bundle_rejects_duplicate_tokens() {
+    let s = setup();
+    let extra_sac = s.env.register_stellar_asset_contract_v2(Address::generate(&s.env));
+    let extra_admin = token::StellarAssetClient::new(&s.env, &extra_sac.address());
+    extra_admin.mint(&s.seller, &100);
+
+    let mut params = default_params(&s);
+    params.bundle = vec![
+        &s.env,
+        (extra_sac.address(), 10i128),
+        (extra_sac.address(), 20i128),
+    ];
+    assert_eq!(
+        s.client.try_create_auction(&params),
+        Err(Ok(AuctionError::DuplicateAsset))
+    );
+    // Listing the primary asset again is the same conflict.
+    params.bundle = vec![&s.env, (s.asset.address.clone(), 10i128)];
+    assert_eq!(
+        s.client.try_create_auction(&params),
+        Err(Ok(AuctionError::DuplicateAsset))
+    );
+}
+
+#[test]
+fn bundle_with_distinct_tokens_is_delivered() {
+    let s = setup();
+    let extra_sac = s.env.register_stellar_asset_contract_v2(Address::generate(&s.env));
+    let extra = token::Client::new(&s.env, &extra_sac.address());
+    let extra_admin = token::StellarAssetClient::new(&s.env, &extra_sac.address());
+    extra_admin.mint(&s.seller, &100);
+
+    let mut params = default_params(&s);
+    params.bundle = vec![&s.env, (extra_sac.address(), 40i128)];
+    let id = s.client.create_auction(&params);
+    assert_eq!(extra.balance(&s.contract_id), 40);
+
+    let alice = fund_bidder(&s, 500);
+    s.client.place_bid(&id, &alice, &100, &None);
+    set_time(&s.env, 1_000);
+    s.client.settle(&id);
+    assert_eq!(s.asset.balance(&alice), 1);
+    assert_eq!(extra.balance(&alice), 40);
+}
+
+#[test]
+fn settle_without_bids_returns_asset() {
+    let s = setup();
+    let id = s.client.create_auction(&default_params(&s));
+    assert_eq!(s.asset.balance(&s.seller), 999_999);
+    assert_eq!(s.asset.balance(&s.contract_id), 1);
+
+    set_time(&s.env, 1_000);
+    s.client.settle(&id);
+    assert_eq!(s.asset.balance(&s.seller), 1_000_000);
+    assert_eq!(s.asset.balance(&s.contract_id), 0);
+}
+
+#[test]
+fn create_with_allowance_pulls_via_transfer_from() {
+    let s = setup();
+    s.asset.approve(&s.seller, &s.contract_id, &1, &1_000);
+    let id = s.client.create_with_allowance(&default_params(&s));
+    assert_eq!(s.asset.balance(&s.contract_id), 1);
+
+    // Without (remaining) allowance the creation fails in the token.
+    assert!(s.client.try_create_with_allowance(&default_params(&s)).is_err());
+
+    set_time(&s.env, 1_000);
+    s.client.settle(&id);
+    assert_eq!(s.asset.balance(&s.seller), 1_000_000);
+}
+
+#[test]
+fn bid_outbid_refund_and_settle() {
+    let s = setup();
+    let id = s.client.create_auction(&default_params(&s));
+    let alice = fund_bidder(&s, 500);
+    let bob = fund_bidder(&s, 500);
+
+    s.client.place_bid(&id, &alice, &100, &None);
+    assert_eq!(s.bid_token.balance(&alice), 400);
+    s.client.place_bid(&id, &bob, &120, &None);
+    // Alice is refunded in full when outbid.
+    assert_eq!(s.bid_token.balance(&alice), 500);
+    assert_eq!(s.bid_token.balance(&s.contract_id), 120);
+
+    set_time(&s.env, 1_000);
+    s.client.settle(&id);
+    assert_eq!(s.asset.balance(&bob), 1);
+    // 2% fee on 120 floors to 2.
+    assert_eq!(s.bid_token.balance(&s.fee_collector), 2);
+    assert_eq!(s.bid_token.balance(&s.seller), 118);
+    assert_eq!(s.bid_token.balance(&s.contract_id), 0);
+}
+
+#[test]
+fn hide_highest_conceals_amount_until_end() {
+    let s = setup();
+    let mut params = default_params(&s);
+    params.hide_highest = true;
+    let id = s.client.create_auction(&params);
+    let alice = fund_bidder(&s, 500);
+
+    s.client.place_bid(&id, &alice, &100, &None);
+    let view = s.client.get_highest_bid(&id);
+    assert!(view.has_bid);
+    assert_eq!(view.bidder, Some(alice.clone()));
+    assert_eq!(view.amount, None);
+
+    // Internal logic still enforces increments against the real amount.
+    let bob = fund_bidder(&s, 500);
+    assert_eq!(
+        s.client.try_place_bid(&id, &bob, &105, &None),
+        Err(Ok(AuctionError::BidTooLow))
+    );
+
+    set_time(&s.env, 1_000);
+    assert_eq!(s.client.get_highest_bid(&id).amount, Some(100));
+}
+
+#[test]
+fn is_reserve_met_flips_with_first_qualifying_bid() {
+    let s = setup();
+    let id = s.client.create_auction(&default_params(&s));
+    assert!(!s.client.is_reserve_met(&id));
+
+    let alice = fund_bidder(&s, 500);
+    s.client.place_bid(&id, &alice, &100, &None);
+    assert!(s.client.is_reserve_met(&id));
+}
+
+#[test]
+fn self_raise_moves_only_the_delta() {
+    let s = setup();
+    let id = s.client.create_auction(&default_params(&s));
+    let alice = fund_bidder(&s, 500);
+
+    s.client.place_bid(&id, &alice, &100, &None);
+    s.client.place_bid(&id, &alice, &120, &None);
+    // Events are per-invocation: the raise emitted a bid but no refund.
+    assert_eq!(
+        event_count(&s, (symbol_short!("refund"), id).into_val(&s.env)),
+        0
+    );
+    assert_eq!(
+        event_count(&s, (symbol_short!("bid"), id).into_val(&s.env)),
+        1
+    );
+    // Only the 20 delta moved; no refund round-trip happened.
+    assert_eq!(s.bid_token.balance(&alice), 380);
+    assert_eq!(s.bid_token.balance(&s.contract_id), 120);
+    let view = s.client.get_highest_bid(&id);
+    assert_eq!(view.bidder, Some(alice.clone()));
+    assert_eq!(view.amount, Some(120));
+
+    // A genuine outbid still refunds the previous leader.
+    let bob = fund_bidder(&s, 500);
+    s.client.place_bid(&id, &bob, &140, &None);
+    assert_eq!(
+        event_count(&s, (symbol_short!("refund"), id).into_val(&s.env)),
+        1
+    );
+    assert_eq!(s.bid_token.balance(&alice), 500);
+}
+
+#[test]
+fn progress_bps_tracks_the_current_window() {
+    let s = setup();
+    let id = s.client.create_auction(&default_params(&s));
+    assert_eq!(s.client.get_progress_bps(&id), 0);
+    set_time(&s.env, 500);
+    assert_eq!(s.client.get_progress_bps(&id), 5_000);
+    set_time(&s.env, 1_200);
+    assert_eq!(s.client.get_progress_bps(&id), 10_000);
+
+    // An extension stretches the window, pulling the percentage back.
+    s.client.extend_auction(&id, &1_000);
+    assert_eq!(s.client.get_progress_bps(&id), 6_000);
+}
+
+#[test]
+fn snapshot_matches_the_individual_getters() {
+    let s = setup();
+    let id = s.client.create_auction(&default_params(&s));
+    let alice = fund_bidder(&s, 500);
+    s.client.place_bid(&id, &alice, &100, &None);
+    set_time(&s.env, 400);
+
+    let snap = s.client.snapshot(&id);
+    assert_eq!(snap.auction, s.client.get_auction(&id));
+    assert_eq!(snap.settled, s.client.is_settled(&id));
+    assert_eq!(snap.highest, s.client.get_highest_bid(&id));
+    assert_eq!(snap.time_remaining, s.client.get_end_time(&id) - 400);
+    assert_eq!(snap.progress_bps, s.client.get_progress_bps(&id));
+    assert_eq!(snap.reserve_met, s.client.is_reserve_met(&id));
+    // 100 leading + 10 minimum increment.
+    assert_eq!(snap.minimum_next_bid, 110);
+}
+
+#[test]
+fn reentrant_token_cannot_double_withdraw() {
+    let s = setup();
+    let token = s.env.register(mock_reentrant::ReentrantToken, ());
+    let token_client = mock_reentrant::ReentrantTokenClient::new(&s.env, &token);
+    let mut params = default_params(&s);
+    params.bid_token = token.clone();
+    params.refund_mode = RefundMode::Pull;
+    let id = s.client.create_auction(&params);
+    let alice = Address::generate(&s.env);
+    let bob = Address::generate(&s.env);
+    token_client.mint(&alice, &1_000);
+    token_client.mint(&bob, &1_000);
+    s.client.place_bid(&id, &alice, &100, &None);
+    s.client.place_bid(&id, &bob, &150, &None);
+    assert_eq!(s.client.get_pending_refund(&alice), 100);
+
+    // The token re-enters `withdraw` from inside the payout transfer. The
+    // pending balance is zeroed before the transfer (and the host refuses
+    // re-entry anyway), so the nested call must come up empty.
+    token_client.arm(&s.contract_id, &alice);
+    s.client.withdraw(&alice);
+    assert!(!token_client.reentry_succeeded());
+    // Exactly one refund was paid out.
+    assert_eq!(token_client.balance(&alice), 1_000);
+    assert_eq!(s.client.get_pending_refund(&alice), 0);
+    assert_eq!(token_client.balance(&s.contract_id), 150);
+}
+
+#[test]
+fn list_by_category_filters_on_the_creation_index() {
+    let s = setup();
+    let mut art = default_params(&s);
+    art.category = symbol_short!("art");
+    let a1 = s.client.create_auction(&art);
+    let mut music = default_params(&s);
+    music.category = symbol_short!("music");
+    let m1 = s.client.create_auction(&music);
+    let a2 = s.client.create_auction(&art);
+    // Uncategorized auctions are not indexed anywhere.
+    s.client.create_auction(&default_params(&s));
+
+    assert_eq!(
+        s.client.list_by_category(&symbol_short!("art")),
+        vec![&s.env, a1, a2]
+    );
+    assert_eq!(
+        s.client.list_by_category(&symbol_short!("music")),
+        vec![&s.env, m1]
+    );
+    assert!(s.client.list_by_category(&symbol_short!("books")).is_empty());
+}
+
+#[test]
+fn stepped_dutch_decay_holds_within_a_step_and_drops_at_boundaries() {
+    let s = setup();
+    let mut params = default_params(&s);
+    params.kind = AuctionKind::Dutch;
+    params.buy_now_price = 500;
+    params.dutch_step = DutchStep {
+        interval: 100,
+        amount: 50,
+    };
+    let id = s.client.create_auction(&params);
+
+    // Flat inside the first interval, then a clean 50 drop per boundary.
+    set_time(&s.env, 1);
+    assert_eq!(s.client.current_price(&id), 500);
+    set_time(&s.env, 99);
+    assert_eq!(s.client.current_price(&id), 500);
+    set_time(&s.env, 100);
+    assert_eq!(s.client.current_price(&id), 450);
+    set_time(&s.env, 199);
+    assert_eq!(s.client.current_price(&id), 450);
+    set_time(&s.env, 200);
+    assert_eq!(s.client.current_price(&id), 400);
+    // A buyer pays the stepped price, not the linear one.
+    let alice = fund_bidder(&s, 500);
+    set_time(&s.env, 250);
+    s.client.buy_now(&id, &alice, &500);
+    assert_eq!(s.bid_token.balance(&alice), 100);
+
+    // The steps bottom out at the reserve floor, never below it.
+    set_time(&s.env, 950);
+    assert_eq!(s.client.current_price(&id), 100);
+}
+
+#[test]
+fn close_unsold_returns_a_floored_dutch_auction_to_the_seller() {
+    let s = setup();
+    let mut params = default_params(&s);
+    params.kind = AuctionKind::Dutch;
+    params.buy_now_price = 500;
+    let id = s.client.create_auction(&params);
+
+    // Still live: nothing to rescue yet.
+    set_time(&s.env, 500);
+    assert_eq!(
+        s.client.try_close_unsold(&id),
+        Err(Ok(AuctionError::AuctionNotEnded))
+    );
+    set_time(&s.env, 1_000);
+    s.client.close_unsold(&id);
+    assert_eq!(s.asset.balance(&s.seller), 1_000_000);
+    assert!(s.client.is_settled(&id));
+}
+
+#[test]
+fn close_unsold_rejects_a_sold_dutch_auction() {
+    let s = setup();
+    let mut params = default_params(&s);
+    params.kind = AuctionKind::Dutch;
+    params.buy_now_price = 500;
+    let id = s.client.create_auction(&params);
+    let alice = fund_bidder(&s, 500);
+    s.client.buy_now(&id, &alice, &500);
+
+    set_time(&s.env, 1_000);
+    assert_eq!(
+        s.client.try_close_unsold(&id),
+        Err(Ok(AuctionError::AlreadySettled))
+    );
+    assert_eq!(s.asset.balance(&alice), 1);
+}
+
+#[test]
+fn allowed_bid_tokens_gate_auction_creation() {
+    let s = setup();
+    let other_token = Address::generate(&s.env);
+    // With a set installed that excludes the default bid token, creation
+    // is refused; widening the set admits it again.
+    s.client
+        .set_allowed_bid_tokens(&vec![&s.env, other_token.clone()]);
+    assert_eq!(
+        s.client.try_create_auction(&default_params(&s)),
+        Err(Ok(AuctionError::BidTokenNotAllowed))
+    );
+    s.client.set_allowed_bid_tokens(&vec![
+        &s.env,
+        other_token,
+        s.bid_token.address.clone(),
+    ]);
+    s.client.create_auction(&default_params(&s));
+    assert_eq!(s.client.get_allowed_bid_tokens().len(), 2);
+
+    // Clearing the set lifts the restriction.
+    s.client.set_allowed_bid_tokens(&Vec::new(&s.env));
+    s.client.create_auction(&default_params(&s));
+}
+
+#[test]
+fn cancel_cooldown_delays_the_sellers_next_listing() {
+    let s = setup();
+    s.client.set_relist_cooldown(&600);
+    let mut params = default_params(&s);
+    params.start_time = 100;
+    let id = s.client.create_auction(&params);
+    set_time(&s.env, 50);
+    s.client.cancel_before_start(&id);
+
+    // Right after cancelling, and still inside the window, creation is
+    // blocked for this seller.
+    assert_eq!(
+        s.client.try_create_auction(&default_params(&s)),
+        Err(Ok(AuctionError::CooldownActive))
+    );
+    set_time(&s.env, 649);
+    assert_eq!(
+        s.client.try_create_auction(&default_params(&s)),
+        Err(Ok(AuctionError::CooldownActive))
+    );
+    // Other sellers are unaffected throughout.
+    let other = Address::generate(&s.env);
+    s.asset
+        .transfer(&s.seller, &other, &1);
+    let mut theirs = default_params(&s);
+    theirs.seller = other;
+    s.client.create_auction(&theirs);
+
+    set_time(&s.env, 650);
+    s.client.create_auction(&default_params(&s));
+}
+
+#[test]
+fn get_stats_aggregates_the_maintained_counters() {
+    let s = setup();
+    let sold = s.client.create_auction(&default_params(&s));
+    let still_open = s.client.create_auction(&default_params(&s));
+    s.client.create_auction(&default_params(&s));
+    let alice = fund_bidder(&s, 500);
+    let bob = fund_bidder(&s, 500);
+    s.client.place_bid(&sold, &alice, &100, &None);
+    s.client.place_bid(&sold, &bob, &120, &None);
+    // Alice counts again on the second auction she bids on.
+    s.client.place_bid(&still_open, &alice, &100, &None);
+
+    set_time(&s.env, 1_000);
+    s.client.settle(&sold);
+    let stats = s.client.get_stats();
+    assert_eq!(stats.total_created, 3);
+    assert_eq!(stats.total_settled, 1);
+    assert_eq!(stats.total_volume, 120);
+    assert_eq!(stats.active_count, 2);
+    assert_eq!(stats.total_bidders, 3);
+}
+
+#[test]
+fn outcome_queries_survive_cleanup() {
+    let s = setup();
+    let id = s.client.create_auction(&default_params(&s));
+    let alice = fund_bidder(&s, 500);
+    s.client.place_bid(&id, &alice, &150, &None);
+    set_time(&s.env, 1_000);
+    s.client.settle(&id);
+    s.client.cleanup(&id);
+
+    // The heavy per-auction record is gone, but the outcome still answers.
+    assert_eq!(
+        s.client.try_get_auction(&id),
+        Err(Ok(AuctionError::AuctionNotFound))
+    );
+    assert_eq!(s.client.get_final_price(&id), 150);
+    assert_eq!(s.client.get_winner(&id), Some(alice));
+}
+
+#[test]
+fn installment_sale_delivers_after_full_payment() {
+    let s = setup();
+    let mut params = default_params(&s);
+    params.installments = InstallmentPlan {
+        down_bps: 2_000,
+        deadline: 5_000,
+        refund_bps: 5_000,
+    };
+    let id = s.client.create_auction(&params);
+    let alice = fund_bidder(&s, 1_000);
+    s.client.place_bid(&id, &alice, &1_000, &None);
+
+    set_time(&s.env, 1_000);
+    s.client.settle(&id);
+    // Settlement kept the 200 down payment and returned the other 800; the
+    // asset stays locked.
+    assert_eq!(s.bid_token.balance(&alice), 800);
+    assert_eq!(s.asset.balance(&alice), 0);
+
+    set_time(&s.env, 2_000);
+    s.client.pay_installment(&id, &300);
+    assert_eq!(s.asset.balance(&alice), 0);
+    // Overpaying past the hammer price is rejected.
+    assert_eq!(
+        s.client.try_pay_installment(&id, &600),
+        Err(Ok(AuctionError::InvalidParams))
+    );
+    set_time(&s.env, 3_000);
+    s.client.pay_installment(&id, &500);
+    // Fully paid: the asset delivers and the 1_000 distributes as usual.
+    assert_eq!(s.asset.balance(&alice), 1);
+    assert_eq!(s.bid_token.balance(&alice), 0);
+    assert_eq!(s.bid_token.balance(&s.seller), 980);
+    assert_eq!(s.bid_token.balance(&s.fee_collector), 20);
+    assert_eq!(s.bid_token.balance(&s.contract_id), 0);
+}
+
+#[test]
+fn installment_default_splits_principal_per_policy() {
+    let s = setup();
+    let mut params = default_params(&s);
+    params.installments = InstallmentPlan {
+        down_bps: 2_000,
+        deadline: 5_000,
+        refund_bps: 5_000,
+    };
+    let id = s.client.create_auction(&params);
+    let alice = fund_bidder(&s, 1_000);
+    s.client.place_bid(&id, &alice, &1_000, &None);
+
+    set_time(&s.env, 1_000);
+    s.client.settle(&id);
+    set_time(&s.env, 2_000);
+    s.client.pay_installment(&id, &100);
+    // The deadline (settlement + 5_000) passes with 300 of 1_000 paid.
+    set_time(&s.env, 6_000);
+    assert_eq!(
+        s.client.try_pay_installment(&id, &700),
+        Err(Ok(AuctionError::AuctionEnded))
+    );
+    s.client.default_installment(&id);
+    // Half the paid-in 300 comes back; the seller keeps the rest and the
+    // asset.
+    assert_eq!(s.bid_token.balance(&alice), 800 - 100 + 150);
+    assert_eq!(s.bid_token.balance(&s.seller), 150);
+    assert_eq!(s.asset.balance(&s.seller), 1_000_000);
+    assert_eq!(s.asset.balance(&alice), 0);
+}
+
+#[test]
+fn net_required_for_nets_off_pending_refunds() {
+    let s = setup();
+    let mut params = default_params(&s);
+    params.refund_mode = RefundMode::Pull;
+    let id = s.client.create_auction(&params);
+    let alice = fund_bidder(&s, 500);
+    let bob = fund_bidder(&s, 500);
+
+    // No pending credit yet: the full floor is needed.
+    assert_eq!(s.client.net_required_for(&id, &alice), 100);
+    s.client.place_bid(&id, &alice, &100, &None);
+    s.client.place_bid(&id, &bob, &120, &None);
+
+    // Alice's displaced 100 is parked; retaking the lead at 130 needs only
+    // 30 of new money. Bob has nothing parked and needs the full amount.
+    assert_eq!(s.client.get_pending_refund(&alice), 100);
+    assert_eq!(s.client.net_required_for(&id, &alice), 30);
+    assert_eq!(s.client.net_required_for(&id, &bob), 130);
+}
+
+#[test]
+fn co_sellers_split_the_proceeds_by_share() {
+    let s = setup();
+    let partner = Address::generate(&s.env);
+    let mut params = default_params(&s);
+    params.co_sellers = vec![
+        &s.env,
+        (s.seller.clone(), 6_000u32),
+        (partner.clone(), 4_000u32),
+    ];
+    let id = s.client.create_auction(&params);
+    let alice = fund_bidder(&s, 1_000);
+    s.client.place_bid(&id, &alice, &1_000, &None);
+
+    set_time(&s.env, 1_000);
+    s.client.settle(&id);
+    // 1_000 sale: 20 fee, then the 980 seller cut splits 60/40.
+    assert_eq!(s.bid_token.balance(&s.seller), 588);
+    assert_eq!(s.bid_token.balance(&partner), 392);
+
+    // Shares that do not sum to 10_000 never make it past creation.
+    let mut bad = default_params(&s);
+    bad.co_sellers = vec![&s.env, (s.seller.clone(), 6_000u32), (partner, 3_000u32)];
+    assert_eq!(
+        s.client.try_create_auction(&bad),
+        Err(Ok(AuctionError::InvalidParams))
+    );
+}
+
+#[test]
+fn would_extend_flags_only_the_anti_snipe_window() {
+    let s = setup();
+    let mut params = default_params(&s);
+    params.anti_snipe.window = 100;
+    params.anti_snipe.period = 300;
+    let id = s.client.create_auction(&params);
+
+    // Ends at 1_000: the window opens at 900 inclusive.
+    assert!(!s.client.would_extend(&id, &899));
+    assert!(s.client.would_extend(&id, &900));
+    assert!(s.client.would_extend(&id, &999));
+    // At or past the end a bid no longer lands at all.
+    assert!(!s.client.would_extend(&id, &1_000));
+}
+
+#[test]
+fn escrow_bonus_pays_displaced_bidders_for_lock_time() {
+    let s = setup();
+    // 100% of the locked amount per day, paid out of the protocol fee.
+    s.client.set_escrow_bonus(&10_000);
+    let mut params = default_params(&s);
+    params.duration = 100_000;
+    let id = s.client.create_auction(&params);
+    let alice = fund_bidder(&s, 1_000);
+    let bob = fund_bidder(&s, 100_000);
+
+    s.client.place_bid(&id, &alice, &1_000, &None);
+    // Alice's 1_000 stays locked for half a day before bob displaces her.
+    set_time(&s.env, 43_200);
+    s.client.place_bid(&id, &bob, &100_000, &None);
+    assert_eq!(s.bid_token.balance(&alice), 1_000);
+
+    set_time(&s.env, 100_000);
+    s.client.settle(&id);
+    // Half a day at 100%/day on 1_000 is a 500 bonus, well inside the
+    // 2_000 fee; the collector keeps the rest and the seller is untouched.
+    assert_eq!(s.bid_token.balance(&alice), 1_000 + 500);
+    assert_eq!(s.bid_token.balance(&s.fee_collector), 1_500);
+    assert_eq!(s.bid_token.balance(&s.seller), 98_000);
+    assert_eq!(s.bid_token.balance(&s.contract_id), 0);
+}
+
+#[test]
+fn escrow_bonus_is_capped_by_the_available_fee() {
+    let s = setup();
+    s.client.set_escrow_bonus(&10_000);
+    let mut params = default_params(&s);
+    params.duration = 100_000;
+    let id = s.client.create_auction(&params);
+    let alice = fund_bidder(&s, 100);
+    let bob = fund_bidder(&s, 110);
+
+    s.client.place_bid(&id, &alice, &100, &None);
+    // A full day locked would earn 100 — far more than the 2 fee the sale
+    // generates.
+    set_time(&s.env, 86_400);
+    s.client.place_bid(&id, &bob, &110, &None);
+    set_time(&s.env, 100_000);
+    s.client.settle(&id);
+    // The bonus scales down to the fee; nothing is paid from thin air.
+    assert_eq!(s.bid_token.balance(&alice), 100 + 2);
+    assert_eq!(s.bid_token.balance(&s.fee_collector), 0);
+    assert_eq!(s.bid_token.balance(&s.seller), 108);
+    assert_eq!(s.bid_token.balance(&s.contract_id), 0);
+}
+
+#[test]
+fn listing_fee_returns_only_with_enough_bidders() {
+    let s = setup();
+    s.client.set_listing_fee(&25, &2);
+    s.bid_admin.mint(&s.seller, &50);
+
+    // Well-attended: two distinct bidders.
+    let busy = s.client.create_auction(&default_params(&s));
+    let alice = fund_bidder(&s, 500);
+    let bob = fund_bidder(&s, 500);
+    s.client.place_bid(&busy, &alice, &100, &None);
+    s.client.place_bid(&busy, &bob, &120, &None);
+
+    // Quiet: a single bidder.
+    let quiet = s.client.create_auction(&default_params(&s));
+    let carol = fund_bidder(&s, 500);
+    s.client.place_bid(&quiet, &carol, &100, &None);
+
+    // Both fees are escrowed up front.
+    assert_eq!(s.bid_token.balance(&s.seller), 0);
+
+    set_time(&s.env, 1_000);
+    s.client.settle(&busy);
+    // 120 sale: 2 protocol fee, 118 to the seller, plus the 25 back.
+    assert_eq!(s.bid_token.balance(&s.seller), 118 + 25);
+    assert_eq!(s.bid_token.balance(&s.fee_collector), 2);
+
+    s.client.settle(&quiet);
+    // 100 sale: the collector keeps the 2 fee and the forfeited 25.
+    assert_eq!(s.bid_token.balance(&s.seller), 118 + 25 + 98);
+    assert_eq!(s.bid_token.balance(&s.fee_collector), 2 + 2 + 25);
+}
+
+#[test]
+fn get_end_time_reflects_extensions() {
+    let s = setup();
+    let mut params = default_params(&s);
+    params.anti_snipe.window = 100;
+    params.anti_snipe.period = 300;
+    let id = s.client.create_auction(&params);
+    assert_eq!(s.client.get_end_time(&id), 1_000);
+
+    s.client.extend_auction(&id, &250);
+    assert_eq!(s.client.get_end_time(&id), 1_250);
+
+    let alice = fund_bidder(&s, 500);
+    set_time(&s.env, 1_200);
+    s.client.place_bid(&id, &alice, &100, &None);
+    assert_eq!(s.client.get_end_time(&id), 1_550);
+}
+
+#[test]
+fn settle_respects_extended_end_plus_delay() {
+    let s = setup();
+    let mut params = default_params(&s);
+    params.anti_snipe.window = 100;
+    params.anti_snipe.period = 200;
+    params.timing.settle_delay = 50;
+    let id = s.client.create_auction(&params);
+    let alice = fund_bidder(&s, 500);
+
+    // A late bid pushes end_time from 1_000 to 1_200.
+    set_time(&s.env, 950);
+    s.client.place_bid(&id, &alice, &100, &None);
+    assert_eq!(s.client.get_auction(&id).end_time, 1_200);
+
+    // The original end plus delay is no longer good enough.
+    set_time(&s.env, 1_050);
+    assert_eq!(
+        s.client.try_settle(&id),
+        Err(Ok(AuctionError::AuctionNotEnded))
+    );
+    set_time(&s.env, 1_249);
+    assert_eq!(
+        s.client.try_settle(&id),
+        Err(Ok(AuctionError::AuctionNotEnded))
+    );
+    set_time(&s.env, 1_250);
+    s.client.settle(&id);
+    assert_eq!(s.asset.balance(&alice), 1);
+}
+
+#[test]
+fn bid_below_reserve_or_increment_rejected() {
+    let s = setup();
+    let id = s.client.create_auction(&default_params(&s));
+    let alice = fund_bidder(&s, 500);
+
+    assert_eq!(
+        s.client.try_place_bid(&id, &alice, &99, &None),
+        Err(Ok(AuctionError::BidTooLow))
+    );
+    s.client.place_bid(&id, &alice, &100, &None);
+    let bob = fund_bidder(&s, 500);
+    assert_eq!(
+        s.client.try_place_bid(&id, &bob, &105, &None),
+        Err(Ok(AuctionError::BidTooLow))
+    );
+}
+
+#[test]
+fn opening_bid_floor_applies_only_to_first_bid() {
+    let s = setup();
+    let mut params = default_params(&s);
+    // 10% above the 100 reserve: the opening bid must be at least 110.
+    params.opening_bid_bps = 1_000;
+    let id = s.client.create_auction(&params);
+    let alice = fund_bidder(&s, 500);
+    let bob = fund_bidder(&s, 500);
+
+    assert_eq!(
+        s.client.try_place_bid(&id, &alice, &109, &None),
+        Err(Ok(AuctionError::BidTooLow))
+    );
+    s.client.place_bid(&id, &alice, &110, &None);
+    // Subsequent bids follow the plain increment rule, not the opening floor.
+    s.client.place_bid(&id, &bob, &120, &None);
+    assert_eq!(s.client.get_highest_bid(&id).amount, Some(120));
+}
+
+#[test]
+fn auction_count_increments_per_create() {
+    let s = setup();
+    assert_eq!(s.client.get_auction_count(), 0);
+    s.client.create_auction(&default_params(&s));
+    assert_eq!(s.client.get_auction_count(), 1);
+    s.client.create_auction(&default_params(&s));
+    assert_eq!(s.client.get_auction_count(), 2);
+}
+
+#[test]
+fn active_auction_cap_frees_slot_on_settle() {
+    let s = setup();
+    s.client.set_max_active_auctions(&2);
+    let first = s.client.create_auction(&default_params(&s));
+    s.client.create_auction(&default_params(&s));
+    assert_eq!(
+        s.client.try_create_auction(&default_params(&s)),
+        Err(Ok(AuctionError::TooManyActiveAuctions))
+    );
+
+    set_time(&s.env, 1_000);
+    s.client.settle(&first);
+    let mut params = default_params(&s);
+    params.duration = 500;
+    s.client.create_auction(&params);
+}
+
+#[test]
+fn list_by_seller_tracks_only_that_seller() {
+    let s = setup();
+    let other = Address::generate(&s.env);
+    let asset_admin = token::StellarAssetClient::new(&s.env, &s.asset.address);
+    asset_admin.mint(&other, &10);
+    let id_a = s.client.create_auction(&default_params(&s));
+    let id_b = s.client.create_auction(&default_params(&s));
+    let mut params = default_params(&s);
+    params.seller = other.clone();
+    let id_c = s.client.create_auction(&params);
+
+    assert_eq!(
+        s.client.list_by_seller(&s.seller, &0, &10),
+        vec![&s.env, id_a, id_b]
+    );
+    assert_eq!(s.client.list_by_seller(&other, &0, &10), vec![&s.env, id_c]);
+    // Pagination clamps to the stored range.
+    assert_eq!(
+        s.client.list_by_seller(&s.seller, &1, &10),
+        vec![&s.env, id_b]
+    );
+    assert_eq!(
+        s.client.list_by_seller(&s.seller, &5, &10),
+        Vec::<u64>::new(&s.env)
+    );
+}
+
+#[test]
+fn increment_schedule_applies_per_threshold() {
+    let s = setup();
+    let mut params = default_params(&s);
+    params.reserve_price = 10;
+    params.increment_schedule = vec![&s.env, (0i128, 1i128), (100i128, 5i128), (1_000i128, 50i128)];
+    let id = s.client.create_auction(&params);
+    let alice = fund_bidder(&s, 5_000);
+    let bob = fund_bidder(&s, 5_000);
+
+    s.client.place_bid(&id, &alice, &10, &None);
+    // Below 100 a +1 step applies.
+    s.client.place_bid(&id, &bob, &11, &None);
+    s.client.place_bid(&id, &alice, &150, &None);
+    // From 100 the step is +5.
+    assert_eq!(
+        s.client.try_place_bid(&id, &bob, &154, &None),
+        Err(Ok(AuctionError::BidTooLow))
+    );
+    s.client.place_bid(&id, &bob, &155, &None);
+    s.client.place_bid(&id, &alice, &1_000, &None);
+    // From 1_000 the step is +50.
+    assert_eq!(
+        s.client.try_place_bid(&id, &bob, &1_049, &None),
+        Err(Ok(AuctionError::BidTooLow))
+    );
+    s.client.place_bid(&id, &bob, &1_050, &None);
+}
+
+#[test]
+fn increment_schedule_must_be_sorted() {
+    let s = setup();
+    let mut params = default_params(&s);
+    params.increment_schedule = vec![&s.env, (100i128, 5i128), (50i128, 1i128)];
+    assert_eq!(
+        s.client.try_create_auction(&params),
+        Err(Ok(AuctionError::InvalidParams))
+    );
+}
+
+#[test]
+fn refunds_honor_alternate_refund_address() {
+    let s = setup();
+    let id = s.client.create_auction(&default_params(&s));
+    let alice = fund_bidder(&s, 500);
+    let custody = Address::generate(&s.env);
+    let bob = fund_bidder(&s, 500);
+
+    s.client
+        .place_bid(&id, &alice, &100, &Some(custody.clone()));
+    s.client.place_bid(&id, &bob, &120, &None);
+    // Alice's escrow lands at the custody address, not back with her.
+    assert_eq!(s.bid_token.balance(&alice), 400);
+    assert_eq!(s.bid_token.balance(&custody), 100);
+}
+
+#[test]
+fn outbid_chain_refunds_are_exact() {
+    let s = setup();
+    let mut params = default_params(&s);
+    params.deposit = 25;
+    let id = s.client.create_auction(&params);
+
+    let bidders: [Address; 5] = core::array::from_fn(|_| fund_bidder(&s, 1_000));
+    let mut amount = 100;
+    for bidder in bidders.iter() {
+        s.client.place_bid(&id, bidder, &amount, &None);
+        amount += 10;
+    }
+    // Every outbid bidder is back to their pre-bid balance minus only the
+    // still-locked deposit.
+    for bidder in bidders.iter().take(4) {
+        assert_eq!(s.bid_token.balance(bidder), 975);
+    }
+    // The contract holds exactly the leader's bid plus all five deposits.
+    assert_eq!(s.bid_token.balance(&s.contract_id), 140 + 5 * 25);
+
+    set_time(&s.env, 1_000);
+    s.client.settle(&id);
+    // Losers get their deposits back; the winner forfeits theirs to the
+    // seller on top of the hammer price.
+    for bidder in bidders.iter().take(4) {
+        assert_eq!(s.bid_token.balance(bidder), 1_000);
+    }
+    assert_eq!(s.bid_token.balance(&bidders[4]), 1_000 - 140 - 25);
+    assert_eq!(s.bid_token.balance(&s.contract_id), 0);
+}
+
+#[test]
+fn blocked_seller_cannot_strand_a_settlement() {
+    let s = setup();
+    let mock = s.env.register(mock_token::MockToken, ());
+    let mock_client = mock_token::MockTokenClient::new(&s.env, &mock);
+    let mut params = default_params(&s);
+    params.bid_token = mock.clone();
+    params.deposit = 25;
+    let id = s.client.create_auction(&params);
+    let alice = Address::generate(&s.env);
+    mock_client.mint(&alice, &500);
+    s.client.place_bid(&id, &alice, &100, &None);
+    mock_client.set_blocked(&s.seller, &true);
+
+    set_time(&s.env, s.client.get_end_time(&id));
+    // The seller's share and the forfeited deposit both park as pending
+    // claims instead of trapping `settle`; delivery still happens.
+    s.client.settle(&id);
+    assert_eq!(s.asset.balance(&alice), 1);
+    assert!(s.client.has_pending(&s.seller));
+
+    mock_client.set_blocked(&s.seller, &false);
+    s.client.withdraw(&s.seller);
+    assert_eq!(mock_client.balance(&s.seller), 98 + 25);
+    assert_eq!(mock_client.balance(&s.contract_id), 0);
+}
+
+#[test]
+fn pull_mode_refund_via_withdraw() {
+    let s = setup();
+    let mut params = default_params(&s);
+    params.refund_mode = RefundMode::Pull;
+    let id = s.client.create_auction(&params);
+    let alice = fund_bidder(&s, 500);
+    let bob = fund_bidder(&s, 500);
+
+    s.client.place_bid(&id, &alice, &100, &None);
+    s.client.place_bid(&id, &bob, &120, &None);
+    assert_escrow_invariants(&s, &[id]);
+    // No inline refund in pull mode; Alice withdraws.
+    assert_eq!(s.bid_token.balance(&alice), 400);
+    s.client.withdraw(&alice);
+    assert_escrow_invariants(&s, &[id]);
+    assert_eq!(s.bid_token.balance(&alice), 500);
+    assert_eq!(
+        s.client.try_withdraw(&alice),
+        Err(Ok(AuctionError::NothingToWithdraw))
+    );
+}
+
+#[test]
+fn has_pending_reflects_outbids_and_withdrawals() {
+    let s = setup();
+    let mut params = default_params(&s);
+    params.refund_mode = RefundMode::Pull;
+    let id = s.client.create_auction(&params);
+    let alice = fund_bidder(&s, 500);
+    let bob = fund_bidder(&s, 500);
+
+    assert!(!s.client.has_pending(&alice));
+    s.client.place_bid(&id, &alice, &100, &None);
+    assert!(!s.client.has_pending(&alice));
+    s.client.place_bid(&id, &bob, &120, &None);
+    assert!(s.client.has_pending(&alice));
+    s.client.withdraw(&alice);
+    assert!(!s.client.has_pending(&alice));
+}
+
+#[test]
+fn pending_refund_query_tracks_accumulation_and_withdrawal() {
+    let s = setup();
+    let mut params = default_params(&s);
+    params.refund_mode = RefundMode::Pull;
+    let id = s.client.create_auction(&params);
+    let alice = fund_bidder(&s, 500);
+    let bob = fund_bidder(&s, 500);
+
+    s.client.place_bid(&id, &alice, &100, &None);
+    s.client.place_bid(&id, &bob, &120, &None);
+    s.client.place_bid(&id, &alice, &150, &None);
+    assert_eq!(s.client.get_pending_refund(&alice), 100);
+    assert_eq!(s.client.get_pending_refund(&bob), 120);
+
+    s.client.withdraw(&alice);
+    assert_eq!(s.client.get_pending_refund(&alice), 0);
+}
+
+#[test]
+fn scheduled_auction_settles_only_after_end_time() {
+    let s = setup();
+    let mut params = default_params(&s);
+    params.start_time = 5_000;
+    params.duration = 1_000;
+    let id = s.client.create_auction(&params);
+
+    // Neither before the start nor mid-window, even with zero bids.
+    assert_eq!(
+        s.client.try_settle(&id),
+        Err(Ok(AuctionError::AuctionNotEnded))
+    );
+    set_time(&s.env, 5_500);
+    assert_eq!(
+        s.client.try_settle(&id),
+        Err(Ok(AuctionError::AuctionNotEnded))
+    );
+    set_time(&s.env, 6_000);
+    s.client.settle(&id);
+    assert_eq!(s.asset.balance(&s.seller), 1_000_000);
+}
+
+#[test]
+fn events_carry_auction_id_for_concurrent_auctions() {
+    let s = setup();
+    let first = s.client.create_auction(&default_params(&s));
+    let second = s.client.create_auction(&default_params(&s));
+    let alice = fund_bidder(&s, 1_000);
+
+    s.client.place_bid(&first, &alice, &100, &None);
+    assert_eq!(
+        event_count(&s, (symbol_short!("bid"), first).into_val(&s.env)),
+        1
+    );
+    s.client.place_bid(&second, &alice, &100, &None);
+    assert_eq!(
+        event_count(&s, (symbol_short!("bid"), second).into_val(&s.env)),
+        1
+    );
+
+    set_time(&s.env, 1_000);
+    s.client.settle(&first);
+    assert_eq!(
+        event_count(&s, (symbol_short!("settled"), first).into_val(&s.env)),
+        1
+    );
+    s.client.settle(&second);
+    assert_eq!(
+        event_count(&s, (symbol_short!("settled"), second).into_val(&s.env)),
+        1
+    );
+}
+
+#[test]
+fn cancel_before_start_returns_asset() {
+    let s = setup();
+    let mut params = default_params(&s);
+    params.start_time = 5_000;
+    let id = s.client.create_auction(&params);
+
+    s.client.cancel_before_start(&id);
+    assert_eq!(s.asset.balance(&s.seller), 1_000_000);
+    assert_eq!(
+        s.client.try_cancel_before_start(&id),
+        Err(Ok(AuctionError::AlreadySettled))
+    );
+}
+
+#[test]
+fn cancel_rejected_once_started() {
+    let s = setup();
+    let mut params = default_params(&s);
+    params.start_time = 5_000;
+    let id = s.client.create_auction(&params);
+
+    set_time(&s.env, 5_000);
+    assert_eq!(
+        s.client.try_cancel_before_start(&id),
+        Err(Ok(AuctionError::AlreadyStarted))
+    );
+}
+
+#[test]
+fn total_volume_accumulates_across_settles() {
+    let s = setup();
+    let alice = fund_bidder(&s, 1_000);
+    assert_eq!(s.client.get_total_volume(), 0);
+
+    let first = s.client.create_auction(&default_params(&s));
+    s.client.place_bid(&first, &alice, &120, &None);
+    let second = s.client.create_auction(&default_params(&s));
+    s.client.place_bid(&second, &alice, &200, &None);
+
+    set_time(&s.env, 1_000);
+    s.client.settle(&first);
+    assert_eq!(s.client.get_total_volume(), 120);
+    s.client.settle(&second);
+    assert_eq!(s.client.get_total_volume(), 320);
+}
+
+#[test]
+fn dutch_price_decays_and_purchase_settles() {
+    let s = setup();
+    let mut params = default_params(&s);
+    params.kind = AuctionKind::Dutch;
+    params.buy_now_price = 1_000;
+    params.duration = 900;
+    let id = s.client.create_auction(&params);
+
+    assert_eq!(s.client.current_price(&id), 1_000);
+    set_time(&s.env, 450);
+    assert_eq!(s.client.current_price(&id), 550);
+
+    let buyer = fund_bidder(&s, 1_000);
+    s.client.buy_now(&id, &buyer, &550);
+    assert_eq!(s.asset.balance(&buyer), 1);
+    assert_eq!(s.bid_token.balance(&s.fee_collector), 11);
+    assert_eq!(s.bid_token.balance(&s.seller), 539);
+    assert_eq!(
+        s.client.try_buy_now(&id, &buyer, &550),
+        Err(Ok(AuctionError::AlreadySettled))
+    );
+}
+
+#[test]
+fn multi_unit_lot_goes_entirely_to_single_winner() {
+    let s = setup();
+    let mut params = default_params(&s);
+    params.asset_amount = 100;
+    let id = s.client.create_auction(&params);
+    assert_eq!(s.asset.balance(&s.contract_id), 100);
+
+    let alice = fund_bidder(&s, 500);
+    s.client.place_bid(&id, &alice, &200, &None);
+    set_time(&s.env, 1_000);
+    s.client.settle(&id);
+    // All 100 units transfer in one settlement; the seller is paid once.
+    assert_eq!(s.asset.balance(&alice), 100);
+    assert_eq!(s.asset.balance(&s.contract_id), 0);
+    assert_eq!(s.bid_token.balance(&s.seller), 196);
+}
+
+#[test]
+fn divisible_partial_fills_and_settle() {
+    let s = setup();
+    let mut params = default_params(&s);
+    params.kind = AuctionKind::Divisible;
+    params.asset_amount = 100;
+    params.reserve_price = 5;
+    let id = s.client.create_auction(&params);
+
+    let alice = fund_bidder(&s, 1_000);
+    let bob = fund_bidder(&s, 1_000);
+    s.client.buy_units(&id, &alice, &30);
+    s.client.buy_units(&id, &bob, &50);
+    assert_eq!(s.asset.balance(&alice), 30);
+    assert_eq!(s.asset.balance(&bob), 50);
+    assert_eq!(
+        s.client.try_buy_units(&id, &alice, &21),
+        Err(Ok(AuctionError::InsufficientRemaining))
+    );
+
+    assert_eq!(s.client.get_remaining_asset(&id), 20);
+
+    set_time(&s.env, 1_000);
+    let seller_asset_before = s.asset.balance(&s.seller);
+    s.client.settle(&id);
+    assert_eq!(s.client.get_remaining_asset(&id), 0);
+    // 400 in proceeds: 2% fee = 8, seller 392, leftover 20 units returned.
+    assert_eq!(s.bid_token.balance(&s.fee_collector), 8);
+    assert_eq!(s.bid_token.balance(&s.seller), 392);
+    assert_eq!(s.asset.balance(&s.seller), seller_asset_before + 20);
+}
+
+#[test]
+fn soft_reserve_allows_bids_but_blocks_sale() {
+    let s = setup();
+    let mut params = default_params(&s);
+    params.reserve_price = 1;
+    params.min_increment = 1;
+    params.soft_reserve = 500;
+    let id = s.client.create_auction(&params);
+    let alice = fund_bidder(&s, 1_000);
+
+    // Lowball bids are welcome while the auction runs.
+    s.client.place_bid(&id, &alice, &100, &None);
+    set_time(&s.env, 1_000);
+    s.client.settle(&id);
+    // ...but below the soft reserve nothing sells: full refund, asset back.
+    assert_eq!(s.bid_token.balance(&alice), 1_000);
+    assert_eq!(s.asset.balance(&s.seller), 1_000_000);
+    assert_eq!(s.asset.balance(&alice), 0);
+}
+
+#[test]
+fn soft_reserve_met_settles_normally() {
+    let s = setup();
+    let mut params = default_params(&s);
+    params.reserve_price = 1;
+    params.soft_reserve = 500;
+    let id = s.client.create_auction(&params);
+    let alice = fund_bidder(&s, 1_000);
+
+    s.client.place_bid(&id, &alice, &600, &None);
+    set_time(&s.env, 1_000);
+    s.client.settle(&id);
+    assert_eq!(s.asset.balance(&alice), 1);
+    assert_eq!(s.bid_token.balance(&s.seller), 588);
+}
+
+#[test]
+fn vickrey_winner_pays_second_price_and_claims() {
+    let s = setup();
+    let mut params = default_params(&s);
+    params.kind = AuctionKind::Vickrey;
+    let id = s.client.create_auction(&params);
+    let alice = fund_bidder(&s, 500);
+    let bob = fund_bidder(&s, 500);
+
+    s.client.place_bid(&id, &alice, &100, &None);
+    s.client.place_bid(&id, &bob, &150, &None);
+
+    set_time(&s.env, 1_000);
+    s.client.settle(&id);
+    // Bob wins but pays Alice's price; the 50 overbid comes back.
+    assert_eq!(s.bid_token.balance(&bob), 400);
+    assert_eq!(s.bid_token.balance(&s.fee_collector), 2);
+    assert_eq!(s.bid_token.balance(&s.seller), 98);
+    // The outcome records the 100 Bob actually paid, not his 150 bid.
+    assert_eq!(s.client.get_final_price(&id), 100);
+    // Asset is pulled, not pushed.
+    assert_eq!(s.asset.balance(&bob), 0);
+    s.client.claim(&id);
+    assert_eq!(s.asset.balance(&bob), 1);
+    assert_eq!(
+        s.client.try_claim(&id),
+        Err(Ok(AuctionError::NothingToWithdraw))
+    );
+}
+
+#[test]
+fn unclaimed_asset_is_forfeited_after_deadline() {
+    let s = setup();
+    let mut params = default_params(&s);
+    params.kind = AuctionKind::Vickrey;
+    params.timing.claim_deadline = 500;
+    let id = s.client.create_auction(&params);
+    let alice = fund_bidder(&s, 500);
+    s.client.place_bid(&id, &alice, &100, &None);
+
+    set_time(&s.env, 1_000);
+    s.client.settle(&id);
+    // Inside the deadline the seller cannot reclaim and the winner can claim.
+    set_time(&s.env, 1_400);
+    assert_eq!(
+        s.client.try_reclaim_unclaimed(&id),
+        Err(Ok(AuctionError::AuctionNotEnded))
+    );
+    s.client.claim(&id);
+    assert_eq!(s.asset.balance(&alice), 1);
+}
+
+#[test]
+fn reclaim_after_deadline_returns_asset_to_seller() {
+    let s = setup();
+    let mut params = default_params(&s);
+    params.kind = AuctionKind::Vickrey;
+    params.timing.claim_deadline = 500;
+    let id = s.client.create_auction(&params);
+    let alice = fund_bidder(&s, 500);
+    s.client.place_bid(&id, &alice, &100, &None);
+
+    set_time(&s.env, 1_000);
+    s.client.settle(&id);
+    set_time(&s.env, 1_500);
+    s.client.reclaim_unclaimed(&id);
+    assert_eq!(s.asset.balance(&s.seller), 1_000_000);
+    // Too late for the winner now.
+    assert_eq!(
+        s.client.try_claim(&id),
+        Err(Ok(AuctionError::NothingToWithdraw))
+    );
+}
+
+#[test]
+fn create_near_u64_max_errors_cleanly() {
+    let s = setup();
+    let mut params = default_params(&s);
+    params.start_time = u64::MAX - 10;
+    params.duration = 100;
+    assert_eq!(
+        s.client.try_create_auction(&params),
+        Err(Ok(AuctionError::TimeOverflow))
+    );
+}
+
+#[test]
+fn extend_near_u64_max_errors_cleanly() {
+    let s = setup();
+    let mut params = default_params(&s);
+    params.start_time = u64::MAX - 2_000;
+    params.duration = 1_000;
+    let id = s.client.create_auction(&params);
+    assert_eq!(
+        s.client.try_extend_auction(&id, &2_000),
+        Err(Ok(AuctionError::TimeOverflow))
+    );
+    // A safe extension still works.
+    s.client.extend_auction(&id, &500);
+}
+
+#[test]
+fn anti_snipe_near_u64_max_errors_cleanly() {
+    let s = setup();
+    let mut params = default_params(&s);
+    params.start_time = u64::MAX - 2_000;
+    params.duration = 1_000;
+    params.anti_snipe.window = 500;
+    params.anti_snipe.period = 5_000;
+    let id = s.client.create_auction(&params);
+
+    let alice = fund_bidder(&s, 500);
+    // Inside the anti-snipe window; the extension would overflow u64.
+    set_time(&s.env, u64::MAX - 1_200);
+    assert_eq!(
+        s.client.try_place_bid(&id, &alice, &100, &None),
+        Err(Ok(AuctionError::TimeOverflow))
+    );
+}
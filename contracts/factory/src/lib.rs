@@ -0,0 +1,200 @@
+#![no_std]
+// Events are published as raw topic tuples; the typed #[contractevent] API
+// will replace them when the event schema is migrated wholesale.
+#![allow(deprecated)]
+
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, symbol_short, vec, Address, BytesN, Env,
+    IntoVal, Symbol, Vec,
+};
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Admin,
+    /// Hash of the uploaded auction template WASM instances are spun from.
+    WasmHash,
+    /// Every instance this factory has deployed, in creation order.
+    Instances,
+    /// Who asked for a given instance to be deployed.
+    Creator(Address),
+    /// Bid tokens instances may be created for; absent means unrestricted.
+    AllowedTokens,
+    /// The bid token an instance was declared for at creation.
+    BidToken(Address),
+    /// `(fee_collector, fee_bps)` every deployed instance is initialized
+    /// with.
+    FeeConfig,
+}
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum FactoryError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    /// The declared bid token is not in the factory's allowed set.
+    BidTokenNotAllowed = 3,
+}
+
+/// Deploys auction instances from a registered template WASM and keeps a
+/// registry of what it has spun up, so the suite's deployments stay
+/// discoverable from one address.
+#[contract]
+pub struct FactoryContract;
+
+#[contractimpl]
+impl FactoryContract {
+    pub fn initialize(
+        env: Env,
+        admin: Address,
+        wasm_hash: BytesN<32>,
+        fee_collector: Address,
+        fee_bps: u32,
+    ) -> Result<(), FactoryError> {
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(FactoryError::AlreadyInitialized);
+        }
+        admin.require_auth();
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage().instance().set(&DataKey::WasmHash, &wasm_hash);
+        env.storage()
+            .instance()
+            .set(&DataKey::FeeConfig, &(fee_collector, fee_bps));
+        Ok(())
+    }
+
+    /// Swap the template future instances are deployed from; running
+    /// instances are unaffected.
+    pub fn set_wasm_hash(env: Env, wasm_hash: BytesN<32>) -> Result<(), FactoryError> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(FactoryError::NotInitialized)?;
+        admin.require_auth();
+        env.storage().instance().set(&DataKey::WasmHash, &wasm_hash);
+        Ok(())
+    }
+
+    /// Restrict future instances to auctions denominated in one of `tokens`;
+    /// an empty vector lifts the restriction. Running instances keep the
+    /// token they were created for.
+    pub fn set_allowed_bid_tokens(
+        env: Env,
+        tokens: Vec<Address>,
+    ) -> Result<(), FactoryError> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(FactoryError::NotInitialized)?;
+        admin.require_auth();
+        if tokens.is_empty() {
+            env.storage().instance().remove(&DataKey::AllowedTokens);
+        } else {
+            env.storage().instance().set(&DataKey::AllowedTokens, &tokens);
+        }
+        Ok(())
+    }
+
+    /// Deploy a fresh instance of the template at the salt-derived address,
+    /// initialize it and record it in the registry. The declared `bid_token`
+    /// must pass the factory's allowed set (when one is installed), and the
+    /// set is pushed into the new instance so it keeps enforcing the
+    /// restriction in its own `create_auction`.
+    pub fn create_instance(
+        env: Env,
+        creator: Address,
+        salt: BytesN<32>,
+        bid_token: Address,
+    ) -> Result<Address, FactoryError> {
+        creator.require_auth();
+        let wasm_hash: BytesN<32> = env
+            .storage()
+            .instance()
+            .get(&DataKey::WasmHash)
+            .ok_or(FactoryError::NotInitialized)?;
+        let (fee_collector, fee_bps): (Address, u32) = env
+            .storage()
+            .instance()
+            .get(&DataKey::FeeConfig)
+            .ok_or(FactoryError::NotInitialized)?;
+        let allowed: Option<Vec<Address>> =
+            env.storage().instance().get(&DataKey::AllowedTokens);
+        if let Some(allowed) = &allowed {
+            if !allowed.contains(&bid_token) {
+                return Err(FactoryError::BidTokenNotAllowed);
+            }
+        }
+        let deployed = env
+            .deployer()
+            .with_current_contract(salt)
+            .deploy_v2(wasm_hash, ());
+        // Initialize in the same transaction as the deploy — an instance
+        // left uninitialized would be anyone's to claim — with the factory
+        // itself as admin, so the bid-token restriction below stays under
+        // factory control.
+        env.invoke_contract::<()>(
+            &deployed,
+            &Symbol::new(&env, "initialize"),
+            vec![
+                &env,
+                env.current_contract_address().into_val(&env),
+                fee_collector.into_val(&env),
+                fee_bps.into_val(&env),
+            ],
+        );
+        if let Some(allowed) = &allowed {
+            env.invoke_contract::<()>(
+                &deployed,
+                &Symbol::new(&env, "set_allowed_bid_tokens"),
+                vec![&env, allowed.into_val(&env)],
+            );
+        }
+        let mut instances: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Instances)
+            .unwrap_or_else(|| Vec::new(&env));
+        instances.push_back(deployed.clone());
+        env.storage()
+            .persistent()
+            .set(&DataKey::Instances, &instances);
+        env.storage()
+            .persistent()
+            .set(&DataKey::Creator(deployed.clone()), &creator);
+        env.storage()
+            .persistent()
+            .set(&DataKey::BidToken(deployed.clone()), &bid_token);
+        env.events()
+            .publish((symbol_short!("deployed"),), (deployed.clone(), creator));
+        Ok(deployed)
+    }
+
+    /// The address `create_instance` would deploy to for `salt`, so
+    /// integrators can wire up references before the deploy lands.
+    pub fn predict_address(env: Env, salt: BytesN<32>) -> Address {
+        env.deployer().with_current_contract(salt).deployed_address()
+    }
+
+    /// Every instance this factory has deployed, in creation order.
+    pub fn list_instances(env: Env) -> Vec<Address> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Instances)
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    /// Who requested the deployment of `instance`, if this factory made it.
+    pub fn get_creator(env: Env, instance: Address) -> Option<Address> {
+        env.storage().persistent().get(&DataKey::Creator(instance))
+    }
+
+    /// The bid token `instance` was declared for at creation.
+    pub fn get_bid_token(env: Env, instance: Address) -> Option<Address> {
+        env.storage().persistent().get(&DataKey::BidToken(instance))
+    }
+}
+
+mod test;
@@ -0,0 +1,113 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::testutils::Address as _;
+use soroban_sdk::{symbol_short, vec, Env, IntoVal};
+
+/// Trivial stub standing in for the auction template: the factory only
+/// cares about a WASM hash, and this keeps the fixture tiny. Generated by
+/// `testdata/gen_template.rs` — see `testdata/README.md` to regenerate.
+const TEMPLATE_WASM: &[u8] = include_bytes!("../testdata/template.wasm");
+
+struct Setup {
+    env: Env,
+    admin: Address,
+    bid_token: Address,
+    client: FactoryContractClient<'static>,
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let bid_token = Address::generate(&env);
+    let fee_collector = Address::generate(&env);
+    let contract_id = env.register(FactoryContract, ());
+    let client = FactoryContractClient::new(&env, &contract_id);
+    let wasm_hash = env.deployer().upload_contract_wasm(TEMPLATE_WASM);
+    client.initialize(&admin, &wasm_hash, &fee_collector, &200);
+    Setup { env, admin, bid_token, client }
+}
+
+#[test]
+fn deploys_independent_instances_and_lists_them() {
+    let s = setup();
+    let creator = Address::generate(&s.env);
+    let first = s.client.create_instance(&creator, &BytesN::from_array(&s.env, &[1; 32]), &s.bid_token);
+    let second = s.client.create_instance(&creator, &BytesN::from_array(&s.env, &[2; 32]), &s.bid_token);
+    assert_ne!(first, second);
+    assert_eq!(s.client.list_instances(), vec![&s.env, first.clone(), second.clone()]);
+    assert_eq!(s.client.get_creator(&first), Some(creator.clone()));
+    assert_eq!(s.client.get_bid_token(&first), Some(s.bid_token.clone()));
+
+    // Both instances answer on their own: they are real, separate contracts.
+    let sum: u64 = s.env.invoke_contract(
+        &first,
+        &symbol_short!("add"),
+        vec![&s.env, 2u64.into_val(&s.env), 3u64.into_val(&s.env)],
+    );
+    assert_eq!(sum, 5);
+    let sum: u64 = s.env.invoke_contract(
+        &second,
+        &symbol_short!("add"),
+        vec![&s.env, 40u64.into_val(&s.env), 2u64.into_val(&s.env)],
+    );
+    assert_eq!(sum, 42);
+}
+
+#[test]
+fn predicted_address_matches_the_deployment() {
+    let s = setup();
+    let creator = Address::generate(&s.env);
+    let salt = BytesN::from_array(&s.env, &[7; 32]);
+    let predicted = s.client.predict_address(&salt);
+    let deployed = s.client.create_instance(&creator, &salt, &s.bid_token);
+    assert_eq!(predicted, deployed);
+    // A different salt derives a different address.
+    assert_ne!(
+        s.client.predict_address(&BytesN::from_array(&s.env, &[8; 32])),
+        predicted
+    );
+}
+
+#[test]
+fn initialize_is_one_shot() {
+    let s = setup();
+    let hash = s.env.deployer().upload_contract_wasm(TEMPLATE_WASM);
+    assert_eq!(
+        s.client
+            .try_initialize(&s.admin, &hash, &Address::generate(&s.env), &200),
+        Err(Ok(FactoryError::AlreadyInitialized))
+    );
+}
+
+#[test]
+fn allowed_bid_tokens_gate_instance_creation() {
+    let s = setup();
+    let creator = Address::generate(&s.env);
+    let other_token = Address::generate(&s.env);
+    s.client
+        .set_allowed_bid_tokens(&vec![&s.env, s.bid_token.clone()]);
+
+    // A disallowed token never deploys; an allowed one does, and the set is
+    // pushed into the instance (the stub template accepts the handshake —
+    // enforcement inside `create_auction` is covered by the auction crate).
+    assert_eq!(
+        s.client.try_create_instance(
+            &creator,
+            &BytesN::from_array(&s.env, &[3; 32]),
+            &other_token
+        ),
+        Err(Ok(FactoryError::BidTokenNotAllowed))
+    );
+    let deployed = s.client.create_instance(
+        &creator,
+        &BytesN::from_array(&s.env, &[3; 32]),
+        &s.bid_token,
+    );
+    assert_eq!(s.client.get_bid_token(&deployed), Some(s.bid_token.clone()));
+
+    // Clearing the set lifts the restriction.
+    s.client.set_allowed_bid_tokens(&Vec::new(&s.env));
+    s.client.create_instance(&creator, &BytesN::from_array(&s.env, &[4; 32]), &other_token);
+}
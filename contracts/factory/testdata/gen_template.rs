@@ -0,0 +1,154 @@
+//! Deterministic generator for `template.wasm`, the stub contract the
+//! factory tests deploy in place of the real auction build.
+//!
+//! The real template would be the `auction` crate compiled to WASM, but the
+//! test suite must not depend on a cross-compilation toolchain, and a
+//! checked-in binary with no source is neither auditable nor reproducible.
+//! This program hand-assembles the fixture byte-for-byte from scratch so the
+//! binary can always be regenerated and diffed:
+//!
+//! ```text
+//! rustc --edition 2021 gen_template.rs -o gen_template
+//! ./gen_template template.wasm
+//! ```
+//!
+//! The module exports `add(a, b)` over small-`u64` host values (tag 6 in
+//! the low byte) — enough for the tests to prove each deployed instance is
+//! a real, independent contract — plus accept-and-ignore stubs for the
+//! factory's post-deploy initialization handshake. It carries the
+//! `contractenvmetav0` section the host requires, declaring interface
+//! protocol 21, and nothing else.
+
+use std::env;
+use std::fs;
+
+/// Unsigned LEB128, the WASM binary format's integer encoding.
+fn uleb(mut v: u32, out: &mut Vec<u8>) {
+    loop {
+        let mut byte = (v & 0x7f) as u8;
+        v >>= 7;
+        if v != 0 {
+            byte |= 0x80;
+        }
+        out.push(byte);
+        if v == 0 {
+            break;
+        }
+    }
+}
+
+fn section(id: u8, payload: &[u8], out: &mut Vec<u8>) {
+    out.push(id);
+    uleb(payload.len() as u32, out);
+    out.extend_from_slice(payload);
+}
+
+fn name(s: &str, out: &mut Vec<u8>) {
+    uleb(s.len() as u32, out);
+    out.extend_from_slice(s.as_bytes());
+}
+
+/// The exported functions: name, parameter count and body. Soroban exports
+/// exchange `i64`-encoded host values exclusively, so a signature is fully
+/// described by its arity.
+fn functions() -> Vec<(&'static str, u32, Vec<u8>)> {
+    // add(a, b): strip the tag byte from two small-u64 vals, add the
+    // payloads, re-tag the sum.
+    let add = vec![
+        0x20, 0x00, // local.get 0
+        0x42, 0x08, // i64.const 8
+        0x88, // i64.shr_u
+        0x20, 0x01, // local.get 1
+        0x42, 0x08, // i64.const 8
+        0x88, // i64.shr_u
+        0x7c, // i64.add
+        0x42, 0x08, // i64.const 8
+        0x86, // i64.shl
+        0x42, 0x06, // i64.const 6 (the small-u64 tag)
+        0x84, // i64.or
+        0x0b, // end
+    ];
+    // The factory initializes every instance it deploys and pushes its
+    // allowed bid-token set down; the stubs accept those calls and return
+    // Void (tag 2) so the handshake succeeds against the fixture.
+    let void = vec![
+        0x42, 0x02, // i64.const 2 (Void)
+        0x0b, // end
+    ];
+    vec![
+        ("add", 2, add),
+        ("initialize", 3, void.clone()),
+        ("set_allowed_bid_tokens", 1, void),
+    ]
+}
+
+fn main() {
+    let path = env::args()
+        .nth(1)
+        .unwrap_or_else(|| "template.wasm".to_string());
+    let funcs = functions();
+
+    let mut module = vec![0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00];
+
+    // Type section: one entry per distinct arity, all (i64^n) -> i64.
+    let mut arities: Vec<u32> = Vec::new();
+    for (_, arity, _) in &funcs {
+        if !arities.contains(arity) {
+            arities.push(*arity);
+        }
+    }
+    let mut types = Vec::new();
+    uleb(arities.len() as u32, &mut types);
+    for arity in &arities {
+        types.push(0x60); // func type
+        uleb(*arity, &mut types);
+        for _ in 0..*arity {
+            types.push(0x7e); // i64
+        }
+        types.push(0x01); // one result
+        types.push(0x7e);
+    }
+    section(1, &types, &mut module);
+
+    // Function section: each function's type index.
+    let mut funcsec = Vec::new();
+    uleb(funcs.len() as u32, &mut funcsec);
+    for (_, arity, _) in &funcs {
+        let type_idx = arities.iter().position(|a| a == arity).unwrap() as u32;
+        uleb(type_idx, &mut funcsec);
+    }
+    section(3, &funcsec, &mut module);
+
+    // Export section.
+    let mut exports = Vec::new();
+    uleb(funcs.len() as u32, &mut exports);
+    for (idx, (export_name, _, _)) in funcs.iter().enumerate() {
+        name(export_name, &mut exports);
+        exports.push(0x00); // function export
+        uleb(idx as u32, &mut exports);
+    }
+    section(7, &exports, &mut module);
+
+    // Code section: no locals anywhere, just the instruction streams.
+    let mut code = Vec::new();
+    uleb(funcs.len() as u32, &mut code);
+    for (_, _, body) in &funcs {
+        uleb(body.len() as u32 + 1, &mut code);
+        code.push(0x00); // zero local declarations
+        code.extend_from_slice(body);
+    }
+    section(10, &code, &mut module);
+
+    // `contractenvmetav0`: XDR ScEnvMetaEntry, interface-version kind (0),
+    // protocol 21, pre-release 0. The host refuses modules without it.
+    let mut meta = Vec::new();
+    name("contractenvmetav0", &mut meta);
+    meta.extend_from_slice(&[
+        0x00, 0x00, 0x00, 0x00, // kind: interface version
+        0x00, 0x00, 0x00, 0x15, // protocol 21
+        0x00, 0x00, 0x00, 0x00, // pre-release 0
+    ]);
+    section(0, &meta, &mut module);
+
+    fs::write(&path, &module).expect("write template.wasm");
+}